@@ -1,45 +1,629 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Fields};
+use syn::{parse_macro_input, Attribute, DeriveInput, Field, Fields, LitStr, Type};
 
-#[proc_macro_derive(Row)]
-pub fn derive_from_row(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+#[derive(Default)]
+struct ContainerAttrs {
+    krate: Option<syn::Path>,
+    table: Option<String>,
+    rename_all: Option<String>,
+}
+
+impl ContainerAttrs {
+    fn krate(&self) -> syn::Path {
+        self.krate
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(::unnamed_qb))
+    }
+
+    fn table_tokens(&self) -> proc_macro2::TokenStream {
+        match &self.table {
+            Some(table) => quote!(Some(#table)),
+            None => quote!(None),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+    skip_insert: bool,
+    flatten: bool,
+}
 
-    if let syn::Data::Struct(ref data) = input.data {
-        if let Fields::Named(ref fields) = data.fields {
-            let builder_fileds = fields.named.iter().map(|field| {
-                let name = &field.ident;
-                quote!(builder.append_binding(self.#name.value()))
-            });
-
-            let names = fields.named.iter().map(|field| {
-                let name = &field.ident;
-                quote!(stringify!(#name))
-            });
-
-            let name = input.ident;
-
-            return TokenStream::from(quote!(
-            impl<'a> crate::query_builder::Row<'a> for #name {
-                fn columns() -> &'static [&'static str] {
-                    &[
-                        #(#names),*
-                    ]
+fn parse_container_attrs(attrs: &[Attribute]) -> syn::Result<ContainerAttrs> {
+    let mut result = ContainerAttrs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("qb")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                result.krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else if meta.path.is_ident("table") {
+                result.table = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("rename_all") {
+                let rule = meta.value()?.parse::<LitStr>()?;
+
+                if !RENAME_RULES.contains(&rule.value().as_str()) {
+                    return Err(syn::Error::new(rule.span(), "unknown `rename_all` rule"));
                 }
 
-                fn into_row(self, builder: &mut crate::query_builder::RowBuilder<'a>) {
-                    #(#builder_fileds);*
+                result.rename_all = Some(rule.value());
+            } else {
+                return Err(meta.error("unknown `qb` attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(result)
+}
+
+fn parse_field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut result = FieldAttrs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("qb")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                result.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                result.skip = true;
+            } else if meta.path.is_ident("skip_insert") {
+                result.skip_insert = true;
+            } else if meta.path.is_ident("flatten") {
+                result.flatten = true;
+            } else {
+                return Err(meta.error("unknown `qb` attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(result)
+}
+
+const RENAME_RULES: &[&str] = &[
+    "snake_case",
+    "lowercase",
+    "UPPERCASE",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "PascalCase",
+    "camelCase",
+];
+
+// Field idents are expected to be snake_case
+fn rename(rule: &str, field: &str) -> Option<String> {
+    let words = field.split('_').filter(|word| !word.is_empty());
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+
+    let renamed = match rule {
+        "snake_case" => field.to_owned(),
+        "lowercase" => field.to_lowercase(),
+        "UPPERCASE" => field.to_uppercase(),
+        "SCREAMING_SNAKE_CASE" => field.to_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "PascalCase" => words.map(capitalize).collect(),
+        "camelCase" => words
+            .enumerate()
+            .map(|(idx, word)| {
+                if idx == 0 {
+                    word.to_owned()
+                } else {
+                    capitalize(word)
                 }
-            }));
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    Some(renamed)
+}
+
+fn mentions_param(tokens: proc_macro2::TokenStream, params: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => params.contains(&&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_param(group.stream(), params),
+        _ => false,
+    })
+}
+
+fn struct_fields<'i>(input: &'i DeriveInput, derive: &str) -> syn::Result<&'i Fields> {
+    match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: fields @ (Fields::Named(_) | Fields::Unnamed(_)),
+            ..
+        }) => Ok(fields),
+        _ => Err(syn::Error::new(
+            input.ident.span(),
+            format!("Only structs with fields can derive `{}`", derive),
+        )),
+    }
+}
+
+fn member(idx: usize, field: &Field) -> syn::Member {
+    match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(idx.into()),
+    }
+}
+
+fn column_name(
+    field: &Field,
+    rename_field: Option<String>,
+    container: &ContainerAttrs,
+) -> syn::Result<String> {
+    match (rename_field, &field.ident, &container.rename_all) {
+        (Some(name), _, _) => Ok(name),
+        (None, Some(ident), rule) => {
+            let ident = ident.to_string();
+            let ident = ident.trim_start_matches("r#");
+
+            Ok(match rule {
+                Some(rule) => rename(rule, ident).unwrap(),
+                None => ident.to_owned(),
+            })
+        }
+        (None, None, _) => Err(syn::Error::new_spanned(
+            field,
+            "tuple struct fields need `#[qb(rename = \"...\")]` or `#[qb(flatten)]`",
+        )),
+    }
+}
+
+// `T` of `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(inner)) if segment.ident == "Option" => Some(inner),
+        _ => None,
+    }
+}
+
+// Struct generics plus the `'__qb` lifetime of the implemented trait, every
+// struct lifetime has to outlive it.
+fn trait_generics(input: &DeriveInput) -> syn::Generics {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    for lifetime in input.generics.lifetimes() {
+        let lifetime = &lifetime.lifetime;
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#lifetime: '__qb));
+    }
+
+    generics
+}
+
+#[derive(Clone)]
+enum Column<'f> {
+    Name(String),
+    // type of a flattened row, and whether it depends on a type parameter
+    Flatten(&'f Type, bool),
+}
+
+// `&["a", "b"]`, with flattened rows the list is built once and kept in a
+// static, per row type (`key`) when the flattened columns depend on its
+// type parameters
+fn columns_tokens(
+    columns: &[Column<'_>],
+    krate: &syn::Path,
+    method: proc_macro2::TokenStream,
+    key: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if columns
+        .iter()
+        .all(|column| matches!(column, Column::Name(_)))
+    {
+        let names = columns.iter().map(|column| match column {
+            Column::Name(name) => name,
+            Column::Flatten(..) => unreachable!(),
+        });
+
+        return quote!(&[#(#names),*]);
+    }
+
+    let generic = columns
+        .iter()
+        .any(|column| matches!(column, Column::Flatten(_, true)));
+    let pushes = columns.iter().map(|column| match column {
+        Column::Name(name) => quote!(columns.push(#name)),
+        Column::Flatten(ty, _) => {
+            quote!(columns.extend_from_slice(<#ty as #krate::Row<'__qb>>::#method()))
         }
+    });
+    let build = quote!({
+        let mut columns = ::std::vec::Vec::new();
+        #(#pushes;)*
+        columns
+    });
+
+    if generic {
+        let method = method.to_string();
+
+        quote!(#krate::__private::intern_columns(
+            (::std::any::TypeId::of::<#key>(), #method),
+            || #build,
+        ))
+    } else {
+        quote!({
+            static COLUMNS: ::std::sync::OnceLock<::std::vec::Vec<&'static str>> =
+                ::std::sync::OnceLock::new();
+
+            COLUMNS.get_or_init(|| #build)
+        })
     }
+}
 
-    TokenStream::from(
+fn derive_row(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(&input, "Row")?;
+    let container = parse_container_attrs(&input.attrs)?;
+    let krate = container.krate();
+
+    let type_params = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+    let mut generics = trait_generics(&input);
+    let where_clause = generics.make_where_clause();
+
+    let mut columns = vec![];
+    let mut builder_fields = vec![];
+    let mut insert_columns = vec![];
+    let mut insert_builder_fields = vec![];
+
+    for (idx, field) in fields.iter().enumerate() {
+        let attrs = parse_field_attrs(&field.attrs)?;
+
+        if attrs.skip {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let member = member(idx, field);
+        let generic = mentions_param(quote!(#ty), &type_params);
+
+        let (column, binding, insert_binding) = if attrs.flatten {
+            if generic {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: #krate::Row<'__qb>));
+            }
+
+            (
+                Column::Flatten(ty, generic),
+                quote!(#krate::Row::into_row(self.#member, builder)),
+                quote!(#krate::Row::into_insert_row(self.#member, builder)),
+            )
+        } else {
+            if generic {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: #krate::ValueExt<'__qb>));
+            }
+
+            let name = column_name(field, attrs.rename, &container)?;
+            let binding = quote!(builder.append_binding(#krate::ValueExt::value(self.#member)));
+
+            (Column::Name(name), binding.clone(), binding)
+        };
+
+        if !attrs.skip_insert {
+            insert_columns.push(column.clone());
+            insert_builder_fields.push(insert_binding);
+        }
+
+        columns.push(column);
+        builder_fields.push(binding);
+    }
+
+    let name = &input.ident;
+    let table = container.table_tokens();
+
+    // lists depending on type parameters are cached by `TypeId`, which needs
+    // `'static` parameters, so lifetimes are erased from the key
+    if columns
+        .iter()
+        .any(|column| matches!(column, Column::Flatten(_, true)))
+    {
+        for param in &type_params {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#param: 'static));
+        }
+    }
+
+    let key_params = input.generics.params.iter().map(|param| match param {
+        syn::GenericParam::Lifetime(_) => quote!('static),
+        syn::GenericParam::Type(param) => {
+            let ident = &param.ident;
+            quote!(#ident)
+        }
+        syn::GenericParam::Const(param) => {
+            let ident = &param.ident;
+            quote!(#ident)
+        }
+    });
+    let key = quote!(#name<#(#key_params),*>);
+    let columns = columns_tokens(&columns, &krate, quote!(columns), &key);
+    let insert_columns = columns_tokens(&insert_columns, &krate, quote!(insert_columns), &key);
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    generics.params.insert(0, syn::parse_quote!('__qb));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+    #[allow(unused_mut, unused_variables)]
+    impl #impl_generics #krate::Row<'__qb> for #name #ty_generics #where_clause {
+        fn columns() -> &'static [&'static str] {
+            #columns
+        }
+
+        fn into_row(self, builder: &mut #krate::RowBuilder<'__qb>) {
+            #(#builder_fields;)*
+        }
+
+        fn insert_columns() -> &'static [&'static str] {
+            #insert_columns
+        }
+
+        fn into_insert_row(self, builder: &mut #krate::RowBuilder<'__qb>) {
+            #(#insert_builder_fields;)*
+        }
+
+        fn table() -> Option<&'static str> {
+            #table
+        }
+    }))
+}
+
+fn derive_patch(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(&input, "Patch")?;
+    let container = parse_container_attrs(&input.attrs)?;
+    let krate = container.krate();
+
+    let type_params = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+    let mut generics = trait_generics(&input);
+    let where_clause = generics.make_where_clause();
+
+    let mut builder_fields = vec![];
+
+    for (idx, field) in fields.iter().enumerate() {
+        let attrs = parse_field_attrs(&field.attrs)?;
+
+        if attrs.skip {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let member = member(idx, field);
+
+        if attrs.flatten {
+            if mentions_param(quote!(#ty), &type_params) {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: #krate::Patch<'__qb>));
+            }
+
+            builder_fields.push(quote!(#krate::Patch::into_patch(self.#member, builder)));
+
+            continue;
+        }
+
+        let name = column_name(field, attrs.rename, &container)?;
+
+        // `None` leaves the column untouched, `Some(None)` of
+        // `Option<Option<T>>` sets it to NULL
+        let (value_ty, binding) = match option_inner(ty) {
+            Some(inner) => (
+                inner,
+                quote!(if let Some(value) = self.#member {
+                    builder.set(#name, #krate::ValueExt::value(value));
+                }),
+            ),
+            None => (
+                ty,
+                quote!(builder.set(#name, #krate::ValueExt::value(self.#member));),
+            ),
+        };
+
+        if mentions_param(quote!(#value_ty), &type_params) {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#value_ty: #krate::ValueExt<'__qb>));
+        }
+
+        builder_fields.push(binding);
+    }
+
+    let name = &input.ident;
+    let table = container.table_tokens();
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    generics.params.insert(0, syn::parse_quote!('__qb));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+    #[allow(unused_variables)]
+    impl #impl_generics #krate::Patch<'__qb> for #name #ty_generics #where_clause {
+        fn into_patch(self, builder: &mut #krate::PatchBuilder<'__qb>) {
+            #(#builder_fields)*
+        }
+
+        fn table() -> Option<&'static str> {
+            #table
+        }
+    }))
+}
+
+fn derive_table(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match struct_fields(&input, "Table")? {
+        Fields::Named(fields) => fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Only structs with named fields can derive `Table`",
+            ))
+        }
+    };
+    let container = parse_container_attrs(&input.attrs)?;
+    let krate = container.krate();
+    let table = container.table.as_ref().ok_or_else(|| {
         syn::Error::new(
             input.ident.span(),
-            "Only structs with named fields can derive `Row`",
+            "`Table` requires `#[qb(table = \"...\")]`",
         )
-        .to_compile_error(),
-    )
+    })?;
+
+    let mut consts = vec![];
+
+    for field in &fields.named {
+        let attrs = parse_field_attrs(&field.attrs)?;
+
+        if attrs.skip || attrs.flatten {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let ident = field.ident.as_ref().unwrap();
+        let const_ident = syn::Ident::new(
+            &ident.to_string().trim_start_matches("r#").to_uppercase(),
+            ident.span(),
+        );
+        let name = column_name(field, attrs.rename, &container)?;
+        let qualified = format!("{}.{}", table, name);
+
+        consts.push(quote!(
+            pub const #const_ident: #krate::TableColumn<#ty> =
+                #krate::TableColumn::new(#table, #name, #qualified);
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote!(
+    impl #impl_generics #name #ty_generics #where_clause {
+        #(#consts)*
+    }
+
+    impl #impl_generics #krate::Table for #name #ty_generics #where_clause {
+        const TABLE: &'static str = #table;
+    }))
+}
+
+fn derive_sqlx_from_row(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(&input, "FromRow")?;
+    let container = parse_container_attrs(&input.attrs)?;
+    let krate = container.krate();
+    let sqlx = quote!(#krate::__private);
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .push(syn::parse_quote!(&'static str: #sqlx::ColumnIndex<__R>));
+
+    let mut field_values = vec![];
+
+    // the database is generic, so every decoded type gets a bound
+    for (idx, field) in fields.iter().enumerate() {
+        let attrs = parse_field_attrs(&field.attrs)?;
+        let ty = &field.ty;
+        let member = member(idx, field);
+
+        let value = if attrs.skip {
+            quote!(::std::default::Default::default())
+        } else if attrs.flatten {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: #sqlx::FromRow<'__r, __R>));
+
+            quote!(#sqlx::FromRow::from_row(row)?)
+        } else {
+            where_clause.predicates.push(syn::parse_quote!(
+                #ty: #sqlx::Decode<'__r, <__R as #sqlx::Row>::Database>
+                    + #sqlx::Type<<__R as #sqlx::Row>::Database>
+            ));
+
+            let name = column_name(field, attrs.rename, &container)?;
+
+            quote!(#sqlx::Row::try_get(row, #name)?)
+        };
+
+        field_values.push(quote!(#member: #value));
+    }
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    generics.params.insert(0, syn::parse_quote!('__r));
+    generics.params.push(syn::parse_quote!(__R: #sqlx::Row));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+    impl #impl_generics #sqlx::FromRow<'__r, __R> for #name #ty_generics #where_clause {
+        fn from_row(row: &'__r __R) -> ::std::result::Result<Self, #sqlx::Error> {
+            Ok(Self {
+                #(#field_values),*
+            })
+        }
+    }))
+}
+
+#[proc_macro_derive(Row, attributes(qb))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive_row(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(Patch, attributes(qb))]
+pub fn derive_from_patch(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive_patch(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(Table, attributes(qb))]
+pub fn derive_from_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive_table(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FromRow, attributes(qb))]
+pub fn derive_from_sqlx_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive_sqlx_from_row(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
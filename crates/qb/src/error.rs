@@ -1,16 +1,72 @@
 #[derive(Debug)]
 pub struct Error {
-    // description: String,
-    // source:
-    // kind: Box<dyn std::error::Error>,
+    kind: ErrorKind,
 }
 
 #[derive(Debug)]
-pub enum ErrorKind {}
+pub enum ErrorKind {
+    /// Field name is not present in an [`AllowList`](crate::AllowList)
+    UnknownField(String),
+    /// Count of `?` placeholders in a [`Raw`](crate::query_builder::raw::Raw)
+    /// differs from count of its bindings
+    RawBindingsMismatch {
+        placeholders: usize,
+        bindings: usize,
+    },
+    /// Named placeholder has no binding
+    MissingNamedBinding(String),
+    /// `{name}` fragment of a raw sql has no value
+    MissingFragment(String),
+    /// Number does not fit into any supported sql type
+    ValueOutOfRange(String),
+    /// Value can't be serialized to json
+    Serialize(String),
+    /// Pagination cursor is malformed or doesn't match the query ordering
+    InvalidCursor(String),
+    /// Value can't be bound by the driver, e.g. a [`CustomValue`](crate::CustomValue)
+    /// without an encoding for it
+    UnsupportedValue(String),
+    /// Argument passed to a builder can't be converted, reported when the
+    /// query is rendered
+    InvalidArg(String),
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
 
 impl std::fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::UnknownField(field) => write!(f, "unknown field `{}`", field),
+            Self::RawBindingsMismatch {
+                placeholders,
+                bindings,
+            } => write!(
+                f,
+                "raw sql has {} placeholders but {} bindings",
+                placeholders, bindings
+            ),
+            Self::MissingNamedBinding(name) => write!(f, "missing binding for `:{}`", name),
+            Self::MissingFragment(name) => write!(f, "missing fragment for `{{{}}}`", name),
+            Self::ValueOutOfRange(value) => write!(f, "value {} is out of range", value),
+            Self::Serialize(err) => write!(f, "failed to serialize value: {}", err),
+            Self::InvalidCursor(reason) => write!(f, "invalid cursor: {}", reason),
+            Self::UnsupportedValue(reason) => write!(f, "unsupported value: {}", reason),
+            Self::InvalidArg(reason) => write!(f, "invalid argument: {}", reason),
+        }
     }
 }
 
@@ -18,16 +74,8 @@ impl std::error::Error for ErrorKind {}
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{}", self.kind)
     }
 }
 
-impl std::error::Error for Error {
-    // fn description(&self) -> &str {
-    //     &self.inner
-    // }
-
-    // fn cause(&self) -> Option<&dyn std::error::Error> {}
-
-    // fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {}
-}
+impl std::error::Error for Error {}
@@ -1,15 +1,42 @@
+// lets `#[derive(Row)]` refer to `::unnamed_qb` inside this crate too
+extern crate self as unnamed_qb;
+
 mod macros;
 pub mod error;
 pub mod prelude;
 mod query_builder;
+#[cfg(feature = "rusqlite")]
+mod rusqlite_ext;
 pub mod sql_dialect;
+#[cfg(feature = "sqlx-runtime")]
+mod sqlx_runtime;
+#[cfg(feature = "tokio-postgres")]
+mod tokio_pg;
 
+pub use query_builder::cast;
 pub use query_builder::raw::RawExt;
 pub use query_builder::select::column::ColumnExt;
+pub use query_builder::select::pagination::Cursor;
+pub use query_builder::AllowList;
+pub use query_builder::ColumnLiteral;
+pub use query_builder::ColumnValue;
 pub use query_builder::Conditions;
+pub use query_builder::CustomValue;
+pub use query_builder::Identifier;
+#[cfg(feature = "json")]
+pub use query_builder::Json;
+pub use query_builder::Patch;
+pub use query_builder::PatchBuilder;
 pub use query_builder::QueryBuilder;
+pub use query_builder::Row;
+pub use query_builder::RowBuilder;
+pub use query_builder::Table;
+pub use query_builder::TableColumn;
+pub use query_builder::Value;
 pub use query_builder::ValueExt;
-pub use unnamed_qb_macro::Row;
+#[cfg(feature = "sqlx")]
+pub use unnamed_qb_macro::FromRow;
+pub use unnamed_qb_macro::{Patch, Row, Table};
 
 #[macro_export]
 macro_rules! qb {
@@ -26,3 +53,11 @@ macro_rules! qb {
     }
   }
 }
+
+// used by the derive macros
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::query_builder::intern_columns;
+    #[cfg(feature = "sqlx")]
+    pub use sqlx::{ColumnIndex, Decode, Error, FromRow, Row, Type};
+}
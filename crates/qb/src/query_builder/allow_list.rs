@@ -0,0 +1,63 @@
+use super::Relation;
+use crate::error::{Error, ErrorKind};
+use std::collections::HashMap;
+
+/// Maps user-facing field names (e.g. from sort or filter parameters) to real
+/// columns. Fields which are not in the list are rejected.
+#[derive(Debug, Default, Clone)]
+pub struct AllowList<'a> {
+    fields: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> AllowList<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow(mut self, field: &'a str, column: &'a str) -> Self {
+        self.fields.insert(field, column);
+
+        self
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.fields.contains_key(field)
+    }
+
+    pub fn column(&self, field: &str) -> Result<Relation<'a>, Error> {
+        self.fields
+            .get(field)
+            .map(|column| Relation::from(*column))
+            .ok_or_else(|| ErrorKind::UnknownField(field.to_owned()).into())
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a str)> for AllowList<'a> {
+    fn from_iter<T: IntoIterator<Item = (&'a str, &'a str)>>(iter: T) -> Self {
+        Self {
+            fields: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AllowList;
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn column() {
+        let list = AllowList::new().allow("created", "users.created_at");
+
+        assert_eq!(list.column("created").unwrap().0, "users.created_at");
+        assert!(list.contains("created"));
+    }
+
+    #[test]
+    fn unknown_field() {
+        let list: AllowList = [("name", "full_name")].into_iter().collect();
+        let err = list.column(r#"a"; drop table x; --"#).unwrap_err();
+
+        assert!(matches!(err.kind(), ErrorKind::UnknownField(_)));
+    }
+}
@@ -1,11 +1,12 @@
 use super::value::Value;
-use super::{qb_arg::Arg, TryIntoArg};
+use super::{deferred_arg, owned_str, qb_arg::Arg, raw::Raw, raw::RawExt, TryIntoArg};
+use std::borrow::Cow;
 
 #[derive(Debug, Clone)]
 pub struct SingleWhereCondition<'a> {
     pub op: ConditionOp,
     pub left: Arg<'a>,
-    pub middle: String,
+    pub middle: Cow<'a, str>,
     pub right: Arg<'a>,
 }
 
@@ -22,6 +23,25 @@ pub enum WhereCondition<'a> {
 }
 
 impl<'a> WhereCondition<'a> {
+    pub fn into_owned(self) -> WhereCondition<'static> {
+        match self {
+            Self::Group(cond) => WhereCondition::Group(GroupedWhereCondition {
+                op: cond.op,
+                conditions: cond
+                    .conditions
+                    .into_iter()
+                    .map(WhereCondition::into_owned)
+                    .collect(),
+            }),
+            Self::Single(cond) => WhereCondition::Single(SingleWhereCondition {
+                op: cond.op,
+                left: cond.left.into_owned(),
+                middle: owned_str(cond.middle),
+                right: cond.right.into_owned(),
+            }),
+        }
+    }
+
     pub fn set_op(&mut self, op: ConditionOp) {
         match self {
             Self::Group(cond) => cond.op = op,
@@ -51,13 +71,33 @@ pub trait TryIntoCondition<'a> {
     fn try_into_condition(self) -> Result<WhereCondition<'a>, ()>;
 }
 
+// a failed conversion is reported when the query is rendered
+fn deferred_condition<'a>(condition: impl TryIntoCondition<'a>) -> WhereCondition<'a> {
+    condition.try_into_condition().unwrap_or_else(|()| {
+        let invalid = Arg::Raw(Raw::invalid("can't be converted into a condition"));
+
+        WhereCondition::Single(SingleWhereCondition {
+            op: ConditionOp::And,
+            left: invalid.clone(),
+            middle: Cow::Borrowed("="),
+            right: invalid,
+        })
+    })
+}
+
+impl<'a> TryIntoCondition<'a> for WhereCondition<'a> {
+    fn try_into_condition(self) -> Result<WhereCondition<'a>, ()> {
+        Ok(self)
+    }
+}
+
 impl<'a, T1: TryIntoArg<'a>, T2: TryIntoArg<'a>> TryIntoCondition<'a> for (T1, T2) {
     fn try_into_condition(self) -> Result<WhereCondition<'a>, ()> {
         Ok(WhereCondition::Single(SingleWhereCondition {
             op: ConditionOp::And,
-            left: self.0.try_into_arg().unwrap(),
-            middle: "=".to_owned(),
-            right: self.1.try_into_arg().unwrap(),
+            left: deferred_arg(self.0),
+            middle: Cow::Borrowed("="),
+            right: deferred_arg(self.1),
         }))
     }
 }
@@ -66,9 +106,9 @@ impl<'a, T1: TryIntoArg<'a>, T2: TryIntoArg<'a>> TryIntoCondition<'a> for (T1, &
     fn try_into_condition(self) -> Result<WhereCondition<'a>, ()> {
         Ok(WhereCondition::Single(SingleWhereCondition {
             op: ConditionOp::And,
-            left: self.0.try_into_arg().unwrap(),
-            middle: self.1.to_owned(),
-            right: self.2.try_into_arg().unwrap(),
+            left: deferred_arg(self.0),
+            middle: Cow::Borrowed(self.1),
+            right: deferred_arg(self.2),
         }))
     }
 }
@@ -79,20 +119,45 @@ pub enum ConditionOp {
     Or,
 }
 
+fn array_condition<'a>(
+    op: ConditionOp,
+    left: impl TryIntoArg<'a>,
+    middle: &'static str,
+    right: Arg<'a>,
+) -> WhereCondition<'a> {
+    WhereCondition::Single(SingleWhereCondition {
+        op,
+        left: deferred_arg(left),
+        middle: Cow::Borrowed(middle),
+        right,
+    })
+}
+
+// `left = any($1)`, so the whole list is bound as a single array parameter
+fn any_condition<'a>(
+    op: ConditionOp,
+    left: impl TryIntoArg<'a>,
+    values: impl Into<Value<'a>>,
+) -> WhereCondition<'a> {
+    let right = "any(?)".raw().bindings([values.into()]);
+
+    array_condition(op, left, "=", Arg::Raw(right))
+}
+
 pub trait PushCondition<'a> {
     fn push_cond(&mut self, cond: WhereCondition<'a>);
 }
 
 pub trait Conditions<'a>: PushCondition<'a> {
     fn and_where(&mut self, condition: impl TryIntoCondition<'a>) -> &mut Self {
-        let condition = condition.try_into_condition().unwrap();
+        let condition = deferred_condition(condition);
         self.push_cond(condition);
 
         self
     }
 
     fn or_where(&mut self, condition: impl TryIntoCondition<'a>) -> &mut Self {
-        let mut condition = condition.try_into_condition().unwrap();
+        let mut condition = deferred_condition(condition);
 
         condition.set_op(ConditionOp::Or);
 
@@ -136,8 +201,8 @@ pub trait Conditions<'a>: PushCondition<'a> {
     fn and_where_null<L: TryIntoArg<'a>>(&mut self, left: L) -> &mut Self {
         self.push_cond(WhereCondition::Single(SingleWhereCondition {
             op: ConditionOp::And,
-            left: left.try_into_arg().unwrap(),
-            middle: "is".to_owned(),
+            left: deferred_arg(left),
+            middle: Cow::Borrowed("is"),
             right: Arg::Value(super::ArgValue::Value(Value::Null)),
         }));
 
@@ -147,8 +212,8 @@ pub trait Conditions<'a>: PushCondition<'a> {
     fn or_where_null<L: TryIntoArg<'a>>(&mut self, left: L) -> &mut Self {
         self.push_cond(WhereCondition::Single(SingleWhereCondition {
             op: ConditionOp::Or,
-            left: <L as TryIntoArg>::try_into_arg(left).unwrap(),
-            middle: "is".to_owned(),
+            left: deferred_arg(left),
+            middle: Cow::Borrowed("is"),
             right: Arg::Value(super::ArgValue::Value(Value::Null)),
         }));
 
@@ -158,8 +223,8 @@ pub trait Conditions<'a>: PushCondition<'a> {
     fn and_where_not_null<L: TryIntoArg<'a>>(&mut self, left: L) -> &mut Self {
         self.push_cond(WhereCondition::Single(SingleWhereCondition {
             op: ConditionOp::And,
-            left: <L as TryIntoArg>::try_into_arg(left).unwrap(),
-            middle: "is not".to_owned(),
+            left: deferred_arg(left),
+            middle: Cow::Borrowed("is not"),
             right: Arg::Value(super::ArgValue::Value(Value::Null)),
         }));
 
@@ -169,11 +234,97 @@ pub trait Conditions<'a>: PushCondition<'a> {
     fn or_where_not_null<L: TryIntoArg<'a>>(&mut self, left: L) -> &mut Self {
         self.push_cond(WhereCondition::Single(SingleWhereCondition {
             op: ConditionOp::Or,
-            left: <L as TryIntoArg>::try_into_arg(left).unwrap(),
-            middle: "is not".to_owned(),
+            left: deferred_arg(left),
+            middle: Cow::Borrowed("is not"),
             right: Arg::Value(super::ArgValue::Value(Value::Null)),
         }));
 
         self
     }
+
+    fn and_where_any<L: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        values: impl Into<Value<'a>>,
+    ) -> &mut Self {
+        self.push_cond(any_condition(ConditionOp::And, left, values));
+
+        self
+    }
+
+    fn or_where_any<L: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        values: impl Into<Value<'a>>,
+    ) -> &mut Self {
+        self.push_cond(any_condition(ConditionOp::Or, left, values));
+
+        self
+    }
+
+    fn and_where_contains<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        right: R,
+    ) -> &mut Self {
+        let right = deferred_arg(right);
+        self.push_cond(array_condition(ConditionOp::And, left, "@>", right));
+
+        self
+    }
+
+    fn or_where_contains<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        right: R,
+    ) -> &mut Self {
+        let right = deferred_arg(right);
+        self.push_cond(array_condition(ConditionOp::Or, left, "@>", right));
+
+        self
+    }
+
+    fn and_where_contained_by<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        right: R,
+    ) -> &mut Self {
+        let right = deferred_arg(right);
+        self.push_cond(array_condition(ConditionOp::And, left, "<@", right));
+
+        self
+    }
+
+    fn or_where_contained_by<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        right: R,
+    ) -> &mut Self {
+        let right = deferred_arg(right);
+        self.push_cond(array_condition(ConditionOp::Or, left, "<@", right));
+
+        self
+    }
+
+    fn and_where_overlaps<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        right: R,
+    ) -> &mut Self {
+        let right = deferred_arg(right);
+        self.push_cond(array_condition(ConditionOp::And, left, "&&", right));
+
+        self
+    }
+
+    fn or_where_overlaps<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
+        &mut self,
+        left: L,
+        right: R,
+    ) -> &mut Self {
+        let right = deferred_arg(right);
+        self.push_cond(array_condition(ConditionOp::Or, left, "&&", right));
+
+        self
+    }
 }
@@ -1,19 +1,77 @@
+mod allow_list;
 pub mod conditions;
 mod qb_arg;
 mod query;
 mod row;
+mod table;
 mod value;
 
+pub use self::allow_list::*;
 pub use self::query::delete::*;
 pub use self::query::insert::*;
 pub use self::query::select::{self, *};
 pub use self::query::update::*;
 pub use conditions::*;
-pub use qb_arg::*;
+pub use qb_arg::{cast::cast, identifier::Identifier, *};
 pub use row::*;
 use std::borrow::Cow;
+pub use table::*;
 pub use value::*;
 
+pub(crate) fn owned_str(value: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(value.into_owned())
+}
+
+/// Table or column name.
+pub trait IntoName<'a> {
+    fn into_name(self) -> Cow<'a, str>;
+}
+
+impl<'a> IntoName<'a> for &'a str {
+    fn into_name(self) -> Cow<'a, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> IntoName<'a> for &'a &'a str {
+    fn into_name(self) -> Cow<'a, str> {
+        Cow::Borrowed(*self)
+    }
+}
+
+impl<'a> IntoName<'a> for &'a String {
+    fn into_name(self) -> Cow<'a, str> {
+        Cow::Borrowed(self.as_str())
+    }
+}
+
+impl<'a> IntoName<'a> for String {
+    fn into_name(self) -> Cow<'a, str> {
+        Cow::Owned(self)
+    }
+}
+
+impl<'a> IntoName<'a> for Cow<'a, str> {
+    fn into_name(self) -> Cow<'a, str> {
+        self
+    }
+}
+
+// queries must stay `Send + Sync` so they can be held across `.await`
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[allow(unused)]
+    fn assert_queries() {
+        assert_send_sync::<SelectQuery>();
+        assert_send_sync::<InsertQuery>();
+        assert_send_sync::<UpdateQuery>();
+        assert_send_sync::<DeleteQuery>();
+        assert_send_sync::<Arg>();
+        assert_send_sync::<Value>();
+    }
+};
+
 pub struct QueryBuilder;
 
 impl QueryBuilder {
@@ -40,9 +98,55 @@ impl QueryBuilder {
         row.into_row(&mut builder);
 
         UpdateQuery {
+            table: R::table().map(Cow::Borrowed),
             columns,
             values: builder.values,
             ..Default::default()
         }
     }
+
+    /// Update which sets only the columns present in the patch, `None` when
+    /// there is nothing to set.
+    pub fn patch<'b, P: Patch<'b>>(patch: P) -> Option<UpdateQuery<'b>> {
+        let mut builder = PatchBuilder::default();
+
+        patch.into_patch(&mut builder);
+
+        if builder.columns.is_empty() {
+            return None;
+        }
+
+        Some(UpdateQuery {
+            table: P::table().map(Cow::Borrowed),
+            columns: builder.columns.into_iter().map(Cow::Borrowed).collect(),
+            values: builder.values,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QueryBuilder;
+    use crate::{sql_dialect::postgres::PostgresSqlDialect, Conditions, ValueExt};
+
+    #[test]
+    fn query_is_send() {
+        let mut sub_query = QueryBuilder::select();
+        sub_query
+            .from("orders")
+            .and_where(("total", ">", 100.value()));
+
+        let mut qb = QueryBuilder::select();
+        qb.from(sub_query).and_where(("id", "=", 1.value()));
+
+        let sql = std::thread::spawn(move || qb.sql::<PostgresSqlDialect>().sql)
+            .join()
+            .unwrap();
+
+        assert_eq!(
+            sql,
+            r#"select * from (select * from "orders" where "total" > $1) where "id" = $2"#
+        );
+    }
 }
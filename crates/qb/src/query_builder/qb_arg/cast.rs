@@ -0,0 +1,57 @@
+use super::{deferred_arg, Arg, ArgValue, TryIntoArg};
+use crate::query_builder::{select::column::Column, select::column::TryIntoColumn, Value};
+#[derive(Debug, Clone)]
+pub struct Cast<'a> {
+    pub arg: Arg<'a>,
+    pub type_name: &'static str,
+}
+
+/// Casts an argument to the sql type, e.g. `cast("id", "text")` or
+/// `cast(ids.value(), "uuid[]")`. The type name is written as is, so it's
+/// a static string rather than user input.
+pub fn cast<'a>(arg: impl TryIntoArg<'a>, type_name: &'static str) -> Cast<'a> {
+    Cast {
+        arg: deferred_arg(arg),
+        type_name,
+    }
+}
+
+impl<'a> Cast<'a> {
+    pub fn into_owned(self) -> Cast<'static> {
+        Cast {
+            arg: self.arg.into_owned(),
+            type_name: self.type_name,
+        }
+    }
+
+    // whether `::type` can be appended without wrapping the argument
+    pub(crate) fn is_simple(&self) -> bool {
+        matches!(
+            self.arg,
+            Arg::Relation(_) | Arg::Identifier(_) | Arg::Value(ArgValue::Value(_)) | Arg::Cast(_)
+        )
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn cast_as(self, type_name: &'static str) -> Cast<'a> {
+        cast(self, type_name)
+    }
+}
+
+impl<'a> TryIntoArg<'a> for Cast<'a> {
+    type E = crate::error::Error;
+
+    fn try_into_arg(self) -> Result<Arg<'a>, Self::E> {
+        Ok(Arg::Cast(Box::new(self)))
+    }
+}
+
+impl<'a> TryIntoColumn<'a> for Cast<'a> {
+    fn try_into_column(self) -> Result<Column<'a>, ()> {
+        Ok(Column {
+            arg: Arg::Cast(Box::new(self)),
+            alias: None,
+        })
+    }
+}
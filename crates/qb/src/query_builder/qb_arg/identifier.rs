@@ -0,0 +1,39 @@
+use super::{owned_str, Arg, TryIntoArg};
+use std::borrow::Cow;
+
+/// Single identifier which is quoted as a whole.
+///
+/// Unlike [`Relation`](super::Relation) it is never split on `.`, so it can
+/// be used for names which contain dots.
+#[derive(Debug, Clone)]
+pub struct Identifier<'a>(pub Cow<'a, str>);
+
+impl<'a> Identifier<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn into_owned(self) -> Identifier<'static> {
+        Identifier(owned_str(self.0))
+    }
+}
+
+impl<'a> From<&'a str> for Identifier<'a> {
+    fn from(value: &'a str) -> Self {
+        Identifier(Cow::Borrowed(value))
+    }
+}
+
+impl<'a> From<String> for Identifier<'a> {
+    fn from(value: String) -> Self {
+        Identifier(Cow::Owned(value))
+    }
+}
+
+impl<'a> TryIntoArg<'a> for Identifier<'a> {
+    type E = crate::error::Error;
+
+    fn try_into_arg(self) -> Result<Arg<'a>, Self::E> {
+        Ok(Arg::Identifier(self))
+    }
+}
@@ -1,18 +1,38 @@
+pub mod cast;
+pub mod identifier;
 pub mod raw;
 pub mod subquery;
 
-use super::value::Value;
+use super::{owned_str, value::Value};
 use std::borrow::Cow;
 
 #[derive(Debug, Clone)]
 pub struct Relation<'a>(pub Cow<'a, str>);
 
+impl<'a> Relation<'a> {
+    pub fn into_owned(self) -> Relation<'static> {
+        Relation(owned_str(self.0))
+    }
+}
+
 impl<'a> From<&'a str> for Relation<'a> {
     fn from(value: &'a str) -> Self {
         Relation(Cow::Borrowed(value))
     }
 }
 
+impl<'a> From<String> for Relation<'a> {
+    fn from(value: String) -> Self {
+        Relation(Cow::Owned(value))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Relation<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        Relation(value)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ArgValue<'a> {
     Value(Value<'a>),
@@ -31,10 +51,37 @@ pub enum SqlKeyword {
 #[derive(Debug, Clone)]
 pub enum Arg<'a> {
     Relation(Relation<'a>),
+    Identifier(identifier::Identifier<'a>),
     Value(ArgValue<'a>),
     Raw(raw::Raw<'a>),
     SubQuery(subquery::SubQuery<'a>),
     Keyword(SqlKeyword),
+    Cast(Box<cast::Cast<'a>>),
+}
+
+impl<'a> ArgValue<'a> {
+    pub fn into_owned(self) -> ArgValue<'static> {
+        match self {
+            Self::Value(value) => ArgValue::Value(value.into_owned()),
+            Self::Values(values) => {
+                ArgValue::Values(values.into_iter().map(Value::into_owned).collect())
+            }
+        }
+    }
+}
+
+impl<'a> Arg<'a> {
+    pub fn into_owned(self) -> Arg<'static> {
+        match self {
+            Self::Relation(relation) => Arg::Relation(relation.into_owned()),
+            Self::Identifier(identifier) => Arg::Identifier(identifier.into_owned()),
+            Self::Value(value) => Arg::Value(value.into_owned()),
+            Self::Raw(raw) => Arg::Raw(raw.into_owned()),
+            Self::SubQuery(sub_query) => Arg::SubQuery(sub_query.into_owned()),
+            Self::Keyword(keyword) => Arg::Keyword(keyword),
+            Self::Cast(cast) => Arg::Cast(Box::new(cast.into_owned())),
+        }
+    }
 }
 
 pub trait TryIntoArg<'a>: Sized {
@@ -43,6 +90,13 @@ pub trait TryIntoArg<'a>: Sized {
     fn try_into_arg(self) -> Result<Arg<'a>, Self::E>;
 }
 
+/// Converts a builder argument, a failed conversion is reported when the
+/// query is rendered instead of panicking in the builder.
+pub(crate) fn deferred_arg<'a>(arg: impl TryIntoArg<'a>) -> Arg<'a> {
+    arg.try_into_arg()
+        .unwrap_or_else(|err| Arg::Raw(raw::Raw::invalid(err)))
+}
+
 impl<'a> TryIntoArg<'a> for &'a str {
     type E = crate::error::Error;
 
@@ -51,6 +105,30 @@ impl<'a> TryIntoArg<'a> for &'a str {
     }
 }
 
+impl<'a> TryIntoArg<'a> for String {
+    type E = crate::error::Error;
+
+    fn try_into_arg(self) -> Result<Arg<'a>, Self::E> {
+        Ok(Arg::Relation(Relation(Cow::Owned(self))))
+    }
+}
+
+impl<'a> TryIntoArg<'a> for Cow<'a, str> {
+    type E = crate::error::Error;
+
+    fn try_into_arg(self) -> Result<Arg<'a>, Self::E> {
+        Ok(Arg::Relation(Relation(self)))
+    }
+}
+
+impl<'a> TryIntoArg<'a> for Relation<'a> {
+    type E = crate::error::Error;
+
+    fn try_into_arg(self) -> Result<Arg<'a>, Self::E> {
+        Ok(Arg::Relation(self))
+    }
+}
+
 impl<'a> TryIntoArg<'a> for Vec<Value<'a>> {
     type E = crate::error::Error;
 
@@ -1,23 +1,174 @@
-use super::{super::value::Value, TryIntoArg};
-use std::borrow::Cow;
+use super::{super::value::Value, deferred_arg, owned_str, Arg, Relation, TryIntoArg};
+use crate::error::{Error, ErrorKind};
+use std::{borrow::Cow, collections::HashMap};
 
-// fn count_bindings(sql: &str) -> usize {
-//     let mut count = 0;
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RawToken<'s> {
+    Sql(&'s str),
+    Positional,
+    Named(&'s str),
+    Fragment(&'s str),
+}
+
+fn is_ident_start(ch: u8) -> bool {
+    ch.is_ascii_alphabetic() || ch == b'_'
+}
+
+fn is_ident_char(ch: u8) -> bool {
+    ch.is_ascii_alphanumeric() || ch == b'_'
+}
+
+fn skip_until(sql: &[u8], from: usize, end: &[u8]) -> usize {
+    let mut pos = from;
+
+    while pos < sql.len() {
+        if sql[pos..].starts_with(end) {
+            return pos + end.len();
+        }
 
-//     // support escaping
-//     for ch in sql.chars() {
-//         if ch == '?' {
-//             count += 1;
-//         }
-//     }
+        pos += 1;
+    }
+
+    sql.len()
+}
+
+// `E'...'` where a backslash escapes the next character
+fn skip_escape_string(sql: &[u8], from: usize) -> usize {
+    let mut pos = from;
+
+    while pos < sql.len() {
+        match sql[pos] {
+            b'\\' => pos += 2,
+            b'\'' => return pos + 1,
+            _ => pos += 1,
+        }
+    }
 
-//     count
-// }
+    sql.len()
+}
+
+fn skip_block_comment(sql: &[u8], from: usize) -> usize {
+    let mut depth = 0;
+    let mut pos = from;
+
+    while pos < sql.len() {
+        if sql[pos..].starts_with(b"/*") {
+            depth += 1;
+            pos += 2;
+        } else if sql[pos..].starts_with(b"*/") {
+            depth -= 1;
+            pos += 2;
+
+            if depth == 0 {
+                return pos;
+            }
+        } else {
+            pos += 1;
+        }
+    }
+
+    sql.len()
+}
+
+// `$$ ... $$` and `$tag$ ... $tag$`
+fn dollar_quote_tag(sql: &[u8], from: usize) -> Option<&[u8]> {
+    let mut pos = from + 1;
+
+    if pos < sql.len() && is_ident_start(sql[pos]) {
+        while pos < sql.len() && is_ident_char(sql[pos]) {
+            pos += 1;
+        }
+    }
+
+    (pos < sql.len() && sql[pos] == b'$').then(|| &sql[from..=pos])
+}
+
+/// Splits raw sql into plain parts and placeholders.
+///
+/// `?` is a positional placeholder, `??` is a literal `?`, `:name` is a
+/// named placeholder and `{name}` is an interpolated fragment. Quoted strings,
+/// quoted identifiers, comments and `:` right after an identifier (array
+/// slices like `arr[lo:hi]`) are left untouched.
+pub(crate) fn tokenize(sql: &str) -> Vec<RawToken<'_>> {
+    let bytes = sql.as_bytes();
+    let mut tokens = vec![];
+    let mut start = 0;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let after_ident = pos > 0 && is_ident_char(bytes[pos - 1]);
+
+        match bytes[pos] {
+            b'E' | b'e' if !after_ident && bytes[pos + 1..].starts_with(b"'") => {
+                pos = skip_escape_string(bytes, pos + 2)
+            }
+            b'\'' => pos = skip_until(bytes, pos + 1, b"'"),
+            b'"' => pos = skip_until(bytes, pos + 1, b"\""),
+            b'-' if bytes[pos..].starts_with(b"--") => pos = skip_until(bytes, pos + 2, b"\n"),
+            b'/' if bytes[pos..].starts_with(b"/*") => pos = skip_block_comment(bytes, pos),
+            b'$' => match dollar_quote_tag(bytes, pos) {
+                Some(tag) => pos = skip_until(bytes, pos + tag.len(), tag),
+                None => pos += 1,
+            },
+            b':' if bytes[pos..].starts_with(b"::") => pos += 2,
+            b':' if !after_ident && pos + 1 < bytes.len() && is_ident_start(bytes[pos + 1]) => {
+                let name_start = pos + 1;
+                let mut name_end = name_start;
+
+                while name_end < bytes.len() && is_ident_char(bytes[name_end]) {
+                    name_end += 1;
+                }
+
+                tokens.push(RawToken::Sql(&sql[start..pos]));
+                tokens.push(RawToken::Named(&sql[name_start..name_end]));
+                start = name_end;
+                pos = name_end;
+            }
+            b'{' if pos + 1 < bytes.len() && is_ident_start(bytes[pos + 1]) => {
+                let name_start = pos + 1;
+                let mut name_end = name_start;
+
+                while name_end < bytes.len() && is_ident_char(bytes[name_end]) {
+                    name_end += 1;
+                }
+
+                if name_end < bytes.len() && bytes[name_end] == b'}' {
+                    tokens.push(RawToken::Sql(&sql[start..pos]));
+                    tokens.push(RawToken::Fragment(&sql[name_start..name_end]));
+                    start = name_end + 1;
+                }
+
+                pos = name_end;
+            }
+            b'?' if bytes[pos..].starts_with(b"??") => {
+                tokens.push(RawToken::Sql(&sql[start..=pos]));
+                start = pos + 2;
+                pos += 2;
+            }
+            b'?' => {
+                tokens.push(RawToken::Sql(&sql[start..pos]));
+                tokens.push(RawToken::Positional);
+                start = pos + 1;
+                pos += 1;
+            }
+            _ => pos += 1,
+        }
+    }
+
+    tokens.push(RawToken::Sql(&sql[start..]));
+    tokens.retain(|token| !matches!(token, RawToken::Sql("")));
+
+    tokens
+}
 
 #[derive(Debug, Clone)]
 pub struct Raw<'a> {
     pub sql: Cow<'a, str>,
     pub bindings: Option<Vec<Value<'a>>>,
+    pub named_bindings: Option<HashMap<Cow<'a, str>, Value<'a>>>,
+    pub fragments: Option<HashMap<Cow<'a, str>, Arg<'a>>>,
+    // conversion error of a builder argument, see `deferred_arg`
+    pub(crate) invalid: Option<String>,
 }
 
 pub trait RawExt<'a> {
@@ -26,27 +177,248 @@ pub trait RawExt<'a> {
 
 impl<'a> RawExt<'a> for &'a str {
     fn raw(self) -> Raw<'a> {
-        Raw {
-            sql: std::borrow::Cow::Borrowed(self),
-            bindings: None,
-        }
+        Raw::new(self)
+    }
+}
+
+impl<'a> RawExt<'a> for String {
+    fn raw(self) -> Raw<'a> {
+        Raw::new(self)
     }
 }
 
 impl<'a> Raw<'a> {
-    // todo: support validate
+    pub fn new(sql: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            sql: sql.into(),
+            bindings: None,
+            named_bindings: None,
+            fragments: None,
+            invalid: None,
+        }
+    }
+
+    /// Raw which fails validation, so a conversion error surfaces when the
+    /// query is rendered.
+    pub(crate) fn invalid(reason: impl std::fmt::Display) -> Self {
+        Self {
+            invalid: Some(reason.to_string()),
+            ..Self::new("")
+        }
+    }
+
+    pub fn into_owned(self) -> Raw<'static> {
+        Raw {
+            sql: owned_str(self.sql),
+            bindings: self
+                .bindings
+                .map(|bindings| bindings.into_iter().map(Value::into_owned).collect()),
+            named_bindings: self.named_bindings.map(|bindings| {
+                bindings
+                    .into_iter()
+                    .map(|(name, value)| (owned_str(name), value.into_owned()))
+                    .collect()
+            }),
+            fragments: self.fragments.map(|fragments| {
+                fragments
+                    .into_iter()
+                    .map(|(name, arg)| (owned_str(name), arg.into_owned()))
+                    .collect()
+            }),
+            invalid: self.invalid,
+        }
+    }
 
     pub fn bindings(mut self, values: impl IntoIterator<Item = Value<'a>>) -> Self {
         self.bindings = Some(values.into_iter().collect());
 
         self
     }
+
+    pub fn named_bindings<N: Into<Cow<'a, str>>>(
+        mut self,
+        values: impl IntoIterator<Item = (N, Value<'a>)>,
+    ) -> Self {
+        self.named_bindings
+            .get_or_insert_with(Default::default)
+            .extend(values.into_iter().map(|(name, value)| (name.into(), value)));
+
+        self
+    }
+
+    pub fn bind(mut self, name: impl Into<Cow<'a, str>>, value: Value<'a>) -> Self {
+        self.named_bindings
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value);
+
+        self
+    }
+
+    /// Interpolates `{name}` with a relation, rendered quoted.
+    pub fn ident(self, name: impl Into<Cow<'a, str>>, relation: impl Into<Cow<'a, str>>) -> Self {
+        self.fragment(name, Relation(relation.into()))
+    }
+
+    /// Interpolates `{name}` with any argument, e.g. a sub query or another
+    /// raw fragment. Its bindings are renumbered by the dialect, an argument
+    /// which can't be converted is reported when the query is rendered.
+    pub fn fragment(mut self, name: impl Into<Cow<'a, str>>, arg: impl TryIntoArg<'a>) -> Self {
+        self.fragments
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), deferred_arg(arg));
+
+        self
+    }
+
+    pub(crate) fn tokens(&self) -> Vec<RawToken<'_>> {
+        tokenize(&self.sql)
+    }
+
+    pub(crate) fn named_binding(&self, name: &str) -> Option<&Value<'a>> {
+        self.named_bindings
+            .as_ref()
+            .and_then(|bindings| bindings.get(name))
+    }
+
+    pub(crate) fn fragment_arg(&self, name: &str) -> Option<&Arg<'a>> {
+        self.fragments
+            .as_ref()
+            .and_then(|fragments| fragments.get(name))
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(reason) = &self.invalid {
+            return Err(ErrorKind::InvalidArg(reason.clone()).into());
+        }
+
+        let mut placeholders = 0;
+
+        for token in self.tokens() {
+            match token {
+                RawToken::Positional => placeholders += 1,
+                RawToken::Named(name) if self.named_binding(name).is_none() => {
+                    return Err(ErrorKind::MissingNamedBinding(name.to_owned()).into());
+                }
+                RawToken::Fragment(name) if self.fragment_arg(name).is_none() => {
+                    return Err(ErrorKind::MissingFragment(name.to_owned()).into());
+                }
+                _ => {}
+            }
+        }
+
+        let bindings = self.bindings.as_ref().map(Vec::len).unwrap_or(0);
+
+        if placeholders != bindings {
+            return Err(ErrorKind::RawBindingsMismatch {
+                placeholders,
+                bindings,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
 }
 
 impl<'a> TryIntoArg<'a> for Raw<'a> {
     type E = crate::error::Error;
 
+    // validated when rendered, so the builders taking it don't fail
     fn try_into_arg(self) -> Result<super::Arg<'a>, Self::E> {
         Ok(super::Arg::Raw(self))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{tokenize, RawToken::*};
+    use crate::{error::ErrorKind, RawExt, ValueExt};
+
+    #[test]
+    fn tokenize_placeholders() {
+        assert_eq!(
+            tokenize("a = ? and b = :b"),
+            vec![Sql("a = "), Positional, Sql(" and b = "), Named("b")]
+        );
+    }
+
+    #[test]
+    fn tokenize_escaped() {
+        assert_eq!(
+            tokenize("data ?? 'key' and x = ?"),
+            vec![Sql("data ?"), Sql(" 'key' and x = "), Positional]
+        );
+    }
+
+    #[test]
+    fn tokenize_skips_quoted_and_comments() {
+        let sql = r#"'?' || "a:b" -- :c ?
+/* ? /* :d */ */ $$?$$ $t$:e$t$ x::int"#;
+
+        assert_eq!(tokenize(sql), vec![Sql(sql)]);
+    }
+
+    #[test]
+    fn tokenize_skips_array_slices() {
+        assert_eq!(
+            tokenize("arr[lo:hi] = :arr"),
+            vec![Sql("arr[lo:hi] = "), Named("arr")]
+        );
+    }
+
+    #[test]
+    fn tokenize_skips_escape_strings() {
+        assert_eq!(
+            tokenize(r"E'it\'s :x ?' || e'\\' || :y"),
+            vec![Sql(r"E'it\'s :x ?' || e'\\' || "), Named("y")]
+        );
+    }
+
+    #[test]
+    fn tokenize_fragments() {
+        assert_eq!(
+            tokenize("{col} = any({?, ?}) and {sub"),
+            vec![
+                Fragment("col"),
+                Sql(" = any({"),
+                Positional,
+                Sql(", "),
+                Positional,
+                Sql("}) and {sub")
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_mismatch() {
+        let raw = "? + ?".raw().bindings(vec![1.value()]);
+
+        assert!(matches!(
+            raw.validate().unwrap_err().kind(),
+            ErrorKind::RawBindingsMismatch {
+                placeholders: 2,
+                bindings: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn validate_missing_named() {
+        let raw = ":a + :b".raw().bind("a", 1.value());
+
+        assert!(matches!(
+            raw.validate().unwrap_err().kind(),
+            ErrorKind::MissingNamedBinding(name) if name == "b"
+        ));
+    }
+
+    #[test]
+    fn validate_missing_fragment() {
+        let raw = "{a} + {b}".raw().ident("a", "tbl.a");
+
+        assert!(matches!(
+            raw.validate().unwrap_err().kind(),
+            ErrorKind::MissingFragment(name) if name == "b"
+        ));
+    }
+}
@@ -5,6 +5,12 @@ use super::{Arg, TryIntoArg};
 #[derive(Debug, Clone)]
 pub struct SubQuery<'a>(pub SelectQuery<'a>);
 
+impl<'a> SubQuery<'a> {
+    pub fn into_owned(self) -> SubQuery<'static> {
+        SubQuery(self.0.into_owned())
+    }
+}
+
 impl<'a> TryIntoArg<'a> for SelectQuery<'a> {
     type E = crate::error::Error;
 
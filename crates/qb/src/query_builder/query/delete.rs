@@ -1,7 +1,10 @@
 use std::borrow::Cow;
 
+#[cfg(feature = "sqlx")]
+use crate::sql_dialect::SqlxDialect;
 use crate::{
-    query_builder::{PushCondition, WhereCondition},
+    error::Error,
+    query_builder::{owned_str, PushCondition, WhereCondition},
     sql_dialect::{Sql, SqlDialect},
     Conditions,
 };
@@ -13,23 +16,61 @@ pub struct DeleteQuery<'a> {
 }
 
 impl<'a> DeleteQuery<'a> {
-    pub fn from(&mut self, table: &'a str) -> &mut Self {
-        self.table = Some(Cow::Borrowed(table));
+    pub fn into_owned(self) -> DeleteQuery<'static> {
+        DeleteQuery {
+            table: self.table.map(owned_str),
+            where_clause: self
+                .where_clause
+                .into_iter()
+                .map(WhereCondition::into_owned)
+                .collect(),
+        }
+    }
+
+    pub fn from(&mut self, table: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.table = Some(table.into());
         self
     }
 
-    pub fn sql<D>(&'a self) -> Sql<'a>
+    /// Renders the query, failing on a raw fragment which doesn't match its
+    /// bindings.
+    pub fn try_sql<'s, D>(&'s self) -> Result<Sql<'s, D::Database>, Error>
     where
-        D: SqlDialect<'a>,
+        D: SqlDialect<'s>,
     {
         let mut builder = D::init();
 
         builder.build_delete(self);
 
-        builder.sql()
+        builder.try_sql()
+    }
+
+    /// Renders the query.
+    ///
+    /// # Panics
+    ///
+    /// On a raw fragment which doesn't match its bindings, see [`Self::try_sql`].
+    pub fn sql<'s, D>(&'s self) -> Sql<'s, D::Database>
+    where
+        D: SqlDialect<'s>,
+    {
+        self.try_sql::<D>().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Renders into [`Sql`] owning its bindings, so the query stays borrowed
+    /// only for the duration of the call.
+    pub fn to_sql<'s, D>(&'s self) -> Sql<'static, D::Database>
+    where
+        D: SqlDialect<'s>,
+    {
+        self.sql::<D>().into_owned()
     }
 
-    pub fn sqlx_qb<D: SqlDialect<'a>>(&'a self) -> D::SqlxQb {
+    #[cfg(feature = "sqlx")]
+    pub fn sqlx_qb<'s, D>(&'s self) -> Result<D::SqlxQb, Error>
+    where
+        D: SqlxDialect<'s>,
+    {
         let mut builder = D::init();
 
         builder.build_delete(self);
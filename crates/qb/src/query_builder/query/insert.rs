@@ -1,8 +1,11 @@
 use std::borrow::Cow;
 
 use crate::query_builder::subquery::SubQuery;
+#[cfg(feature = "sqlx")]
+use crate::sql_dialect::SqlxDialect;
 use crate::{
-    query_builder::{Row, RowBuilder, Value},
+    error::Error,
+    query_builder::{owned_str, IntoName, Row, RowBuilder, Value},
     sql_dialect::{Sql, SqlDialect},
 };
 
@@ -31,28 +34,57 @@ impl<'a> Default for InsertType<'a> {
 #[derive(Debug, Default, Clone)]
 pub struct InsertQuery<'a> {
     pub table: Option<Cow<'a, str>>,
-    pub ordered_columns: Option<Vec<&'a str>>,
+    pub ordered_columns: Option<Vec<Cow<'a, str>>>,
     pub inner: InsertType<'a>,
 }
 
 impl<'a> InsertQuery<'a> {
-    pub fn into_(&mut self, table: &'a str) -> &mut Self {
-        self.table = Some(Cow::Borrowed(table));
+    pub fn into_owned(self) -> InsertQuery<'static> {
+        InsertQuery {
+            table: self.table.map(owned_str),
+            ordered_columns: self
+                .ordered_columns
+                .map(|columns| columns.into_iter().map(owned_str).collect()),
+            inner: match self.inner {
+                InsertType::WithValues(insert) => InsertType::WithValues(InsertWithValues {
+                    bindings: insert.bindings.into_iter().map(Value::into_owned).collect(),
+                }),
+                InsertType::FromSubQuery(insert) => InsertType::FromSubQuery(InsertFromSubQuery {
+                    sub_query: insert.sub_query.into_owned(),
+                }),
+            },
+        }
+    }
+
+    pub fn into_(&mut self, table: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.table = Some(table.into());
 
         self
     }
 
+    fn table_from_row<R: Row<'a>>(&mut self) {
+        if self.table.is_none() {
+            self.table = R::table().map(Cow::Borrowed);
+        }
+    }
+
     pub fn value<R: Row<'a>>(&mut self, row: R) -> &mut Self {
+        self.table_from_row::<R>();
+
         match &mut self.inner {
             InsertType::FromSubQuery(_) => {
                 panic!("cant")
             }
             InsertType::WithValues(insert) => {
-                self.ordered_columns =
-                    Some(R::columns().into_iter().map(|column| *column).collect());
+                self.ordered_columns = Some(
+                    R::insert_columns()
+                        .iter()
+                        .map(|column| Cow::Borrowed(*column))
+                        .collect(),
+                );
                 let mut builder = RowBuilder::default();
 
-                row.into_row(&mut builder);
+                row.into_insert_row(&mut builder);
 
                 insert.bindings.extend(builder.values);
             }
@@ -62,17 +94,23 @@ impl<'a> InsertQuery<'a> {
     }
 
     pub fn values<R: Row<'a>>(&mut self, rows: impl IntoIterator<Item = R>) -> &mut Self {
+        self.table_from_row::<R>();
+
         match &mut self.inner {
             InsertType::FromSubQuery(_) => {
                 panic!("cant")
             }
             InsertType::WithValues(insert) => {
-                self.ordered_columns =
-                    Some(R::columns().into_iter().map(|column| *column).collect());
+                self.ordered_columns = Some(
+                    R::insert_columns()
+                        .iter()
+                        .map(|column| Cow::Borrowed(*column))
+                        .collect(),
+                );
 
                 for row in rows.into_iter() {
                     let mut builder = RowBuilder::default();
-                    row.into_row(&mut builder);
+                    row.into_insert_row(&mut builder);
 
                     insert.bindings.extend(builder.values);
                 }
@@ -82,28 +120,33 @@ impl<'a> InsertQuery<'a> {
         self
     }
 
-    pub fn push_column(&mut self, column: &'a str) -> &mut Self {
+    pub fn push_column(&mut self, column: impl Into<Cow<'a, str>>) -> &mut Self {
         if let Some(columns) = &mut self.ordered_columns {
-            columns.push(column);
+            columns.push(column.into());
         } else {
-            self.ordered_columns = Some(vec![column]);
+            self.ordered_columns = Some(vec![column.into()]);
         }
 
         self
     }
 
-    pub fn extend_columns(&mut self, new_columns: impl IntoIterator<Item = &'a &'a str>) -> &mut Self {
+    pub fn extend_columns(
+        &mut self,
+        new_columns: impl IntoIterator<Item = impl IntoName<'a>>,
+    ) -> &mut Self {
+        let new_columns = new_columns.into_iter().map(IntoName::into_name);
+
         if let Some(columns) = &mut self.ordered_columns {
             columns.extend(new_columns);
         } else {
-            self.ordered_columns = Some(new_columns.into_iter().map(|column| *column).collect());
+            self.ordered_columns = Some(new_columns.collect());
         }
 
         self
     }
 
-    pub fn columns(&mut self, columns: impl IntoIterator<Item = &'a &'a str>) -> &mut Self {
-        self.ordered_columns = Some(columns.into_iter().map(|column| *column).collect());
+    pub fn columns(&mut self, columns: impl IntoIterator<Item = impl IntoName<'a>>) -> &mut Self {
+        self.ordered_columns = Some(columns.into_iter().map(IntoName::into_name).collect());
 
         self
     }
@@ -114,18 +157,45 @@ impl<'a> InsertQuery<'a> {
         self
     }
 
-    pub fn sql<D>(&'a self) -> Sql<'a>
+    /// Renders the query, failing on a raw fragment which doesn't match its
+    /// bindings.
+    pub fn try_sql<'s, D>(&'s self) -> Result<Sql<'s, D::Database>, Error>
     where
-        D: SqlDialect<'a>,
+        D: SqlDialect<'s>,
     {
         let mut builder = D::init();
 
         builder.build_insert(self);
 
-        builder.sql()
+        builder.try_sql()
     }
 
-    pub fn sqlx_qb<D: SqlDialect<'a>>(&'a self) -> D::SqlxQb {
+    /// Renders the query.
+    ///
+    /// # Panics
+    ///
+    /// On a raw fragment which doesn't match its bindings, see [`Self::try_sql`].
+    pub fn sql<'s, D>(&'s self) -> Sql<'s, D::Database>
+    where
+        D: SqlDialect<'s>,
+    {
+        self.try_sql::<D>().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Renders into [`Sql`] owning its bindings, so the query stays borrowed
+    /// only for the duration of the call.
+    pub fn to_sql<'s, D>(&'s self) -> Sql<'static, D::Database>
+    where
+        D: SqlDialect<'s>,
+    {
+        self.sql::<D>().into_owned()
+    }
+
+    #[cfg(feature = "sqlx")]
+    pub fn sqlx_qb<'s, D>(&'s self) -> Result<D::SqlxQb, Error>
+    where
+        D: SqlxDialect<'s>,
+    {
         let mut builder = D::init();
 
         builder.build_insert(self);
@@ -1,4 +1,4 @@
-use crate::query_builder::{Arg, Relation, TryIntoArg};
+use crate::query_builder::{deferred_arg, owned_str, raw::Raw, Arg, Identifier, Relation};
 use std::borrow::Cow;
 
 use super::SelectQuery;
@@ -9,20 +9,42 @@ pub struct Column<'a> {
     pub alias: Option<Cow<'a, str>>,
 }
 
+impl<'a> Column<'a> {
+    pub fn into_owned(self) -> Column<'static> {
+        Column {
+            arg: self.arg.into_owned(),
+            alias: self.alias.map(owned_str),
+        }
+    }
+}
+
 pub trait ColumnExt<'a> {
-    fn alias(self, alias: &'a str) -> Column<'a>;
-    fn as_(self, alias: &'a str) -> Column<'a>;
+    fn alias(self, alias: impl Into<Cow<'a, str>>) -> Column<'a>;
+    fn as_(self, alias: impl Into<Cow<'a, str>>) -> Column<'a>;
 }
 
 impl<'a> ColumnExt<'a> for &'a str {
-    fn alias(self, alias: &'a str) -> Column<'a> {
+    fn alias(self, alias: impl Into<Cow<'a, str>>) -> Column<'a> {
         Column {
             arg: Arg::Relation(Relation(Cow::Borrowed(self))),
-            alias: Some(Cow::Borrowed(alias)),
+            alias: Some(alias.into()),
         }
     }
 
-    fn as_(self, alias: &'a str) -> Column<'a> {
+    fn as_(self, alias: impl Into<Cow<'a, str>>) -> Column<'a> {
+        Self::alias(self, alias)
+    }
+}
+
+impl<'a> ColumnExt<'a> for String {
+    fn alias(self, alias: impl Into<Cow<'a, str>>) -> Column<'a> {
+        Column {
+            arg: Arg::Relation(Relation(Cow::Owned(self))),
+            alias: Some(alias.into()),
+        }
+    }
+
+    fn as_(self, alias: impl Into<Cow<'a, str>>) -> Column<'a> {
         Self::alias(self, alias)
     }
 }
@@ -31,10 +53,18 @@ pub trait TryIntoColumn<'a> {
     fn try_into_column(self) -> Result<Column<'a>, ()>;
 }
 
+// a failed conversion is reported when the query is rendered
+pub(crate) fn deferred_column<'a>(column: impl TryIntoColumn<'a>) -> Column<'a> {
+    column.try_into_column().unwrap_or_else(|()| Column {
+        arg: Arg::Raw(Raw::invalid("can't be converted into a column")),
+        alias: None,
+    })
+}
+
 impl<'a> TryIntoColumn<'a> for &'a str {
     fn try_into_column(self) -> Result<Column<'a>, ()> {
         Ok(Column {
-            arg: self.try_into_arg().unwrap(),
+            arg: deferred_arg(self),
             alias: None,
         })
     }
@@ -43,7 +73,16 @@ impl<'a> TryIntoColumn<'a> for &'a str {
 impl<'a> TryIntoColumn<'a> for &'a String {
     fn try_into_column(self) -> Result<Column<'a>, ()> {
         Ok(Column {
-            arg: self.try_into_arg().unwrap(),
+            arg: deferred_arg(self.as_str()),
+            alias: None,
+        })
+    }
+}
+
+impl<'a> TryIntoColumn<'a> for String {
+    fn try_into_column(self) -> Result<Column<'a>, ()> {
+        Ok(Column {
+            arg: deferred_arg(self),
             alias: None,
         })
     }
@@ -52,17 +91,43 @@ impl<'a> TryIntoColumn<'a> for &'a String {
 impl<'a> TryIntoColumn<'a> for &'a &'a str {
     fn try_into_column(self) -> Result<Column<'a>, ()> {
         Ok(Column {
-            arg: (*self).try_into_arg().unwrap(),
+            arg: deferred_arg(*self),
             alias: None,
         })
     }
 }
 
+impl<'a> TryIntoColumn<'a> for Relation<'a> {
+    fn try_into_column(self) -> Result<Column<'a>, ()> {
+        Ok(Column {
+            arg: Arg::Relation(self),
+            alias: None,
+        })
+    }
+}
+
+impl<'a> TryIntoColumn<'a> for Identifier<'a> {
+    fn try_into_column(self) -> Result<Column<'a>, ()> {
+        Ok(Column {
+            arg: Arg::Identifier(self),
+            alias: None,
+        })
+    }
+}
+
+impl<'a> TryIntoColumn<'a> for Raw<'a> {
+    fn try_into_column(self) -> Result<Column<'a>, ()> {
+        Ok(Column {
+            arg: Arg::Raw(self),
+            alias: None,
+        })
+    }
+}
 
 impl<'a> TryIntoColumn<'a> for SelectQuery<'a> {
     fn try_into_column(self) -> Result<Column<'a>, ()> {
         Ok(Column {
-            arg: self.try_into_arg().unwrap(),
+            arg: deferred_arg(self),
             alias: None,
         })
     }
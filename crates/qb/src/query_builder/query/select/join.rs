@@ -1,4 +1,4 @@
-use crate::query_builder::Arg;
+use crate::query_builder::{owned_str, Arg};
 use std::borrow::Cow;
 
 #[derive(Debug, Clone)]
@@ -15,3 +15,17 @@ pub enum Join<'a> {
     Regular(RegularJoin<'a>),
     // Raw
 }
+
+impl<'a> Join<'a> {
+    pub fn into_owned(self) -> Join<'static> {
+        match self {
+            Self::Regular(join) => Join::Regular(RegularJoin {
+                join_type: join.join_type,
+                table: owned_str(join.table),
+                left: join.left.into_owned(),
+                op: owned_str(join.op),
+                right: join.right.into_owned(),
+            }),
+        }
+    }
+}
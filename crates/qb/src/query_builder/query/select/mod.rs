@@ -1,19 +1,23 @@
 pub mod column;
 pub mod join;
 pub mod ordering;
+pub mod pagination;
 
 use crate::query_builder::conditions;
+#[cfg(feature = "sqlx")]
+use crate::sql_dialect::SqlxDialect;
 use crate::{
-    query_builder::{Arg, PushCondition, TryIntoArg},
+    error::Error,
+    query_builder::{deferred_arg, owned_str, raw::Raw, Arg, PushCondition, Row, TryIntoArg},
     sql_dialect::{Sql, SqlDialect},
     Conditions,
 };
-use std::{borrow::Cow, rc::Rc};
+use std::borrow::Cow;
 
 #[derive(Debug, Default, Clone)]
 pub struct SelectQuery<'a> {
     pub columns: Option<Vec<column::Column<'a>>>,
-    pub table: Option<Rc<Arg<'a>>>,
+    pub table: Option<Box<Arg<'a>>>,
     pub joins: Option<Vec<join::Join<'a>>>,
     pub where_: Vec<conditions::WhereCondition<'a>>,
     pub limit: Option<usize>,
@@ -24,16 +28,43 @@ pub struct SelectQuery<'a> {
 }
 
 impl<'a> SelectQuery<'a> {
+    pub fn into_owned(self) -> SelectQuery<'static> {
+        SelectQuery {
+            columns: self.columns.map(|columns| {
+                columns
+                    .into_iter()
+                    .map(column::Column::into_owned)
+                    .collect()
+            }),
+            table: self.table.map(|table| Box::new(table.into_owned())),
+            joins: self
+                .joins
+                .map(|joins| joins.into_iter().map(join::Join::into_owned).collect()),
+            where_: self
+                .where_
+                .into_iter()
+                .map(conditions::WhereCondition::into_owned)
+                .collect(),
+            limit: self.limit,
+            offset: self.offset,
+            ordering: self.ordering.map(|ordering| {
+                ordering
+                    .into_iter()
+                    .map(ordering::Ordering::into_owned)
+                    .collect()
+            }),
+            group_by: self
+                .group_by
+                .map(|group_by| group_by.into_iter().map(Arg::into_owned).collect()),
+            alias: self.alias.map(owned_str),
+        }
+    }
+
     pub fn columns(
         &mut self,
         columns: impl IntoIterator<Item = impl column::TryIntoColumn<'a>>,
     ) -> &mut Self {
-        self.columns = Some(
-            columns
-                .into_iter()
-                .map(|a| a.try_into_column().unwrap())
-                .collect(),
-        );
+        self.columns = Some(columns.into_iter().map(column::deferred_column).collect());
 
         self
     }
@@ -42,9 +73,7 @@ impl<'a> SelectQuery<'a> {
         &mut self,
         new_columns: impl IntoIterator<Item = impl column::TryIntoColumn<'a>>,
     ) -> &mut Self {
-        let columns_iter = new_columns
-            .into_iter()
-            .map(|column| column.try_into_column().unwrap());
+        let columns_iter = new_columns.into_iter().map(column::deferred_column);
         if let Some(columns) = &mut self.columns {
             columns.extend(columns_iter);
         } else {
@@ -56,16 +85,22 @@ impl<'a> SelectQuery<'a> {
 
     pub fn push_column(&mut self, column: impl column::TryIntoColumn<'a>) -> &mut Self {
         if let Some(columns) = &mut self.columns {
-            columns.push(column.try_into_column().unwrap());
+            columns.push(column::deferred_column(column));
         } else {
-            self.columns = Some(vec![column.try_into_column().unwrap()]);
+            self.columns = Some(vec![column::deferred_column(column)]);
         }
 
         self
     }
 
+    /// Selects the columns of `R`, so the column list can't drift apart from
+    /// the struct decoding the result.
+    pub fn select_row<R: Row<'a>>(&mut self) -> &mut Self {
+        self.columns(R::columns())
+    }
+
     pub fn from<T: TryIntoArg<'a>>(&mut self, table: T) -> &mut Self {
-        self.table = Some(Rc::new(<T as TryIntoArg>::try_into_arg(table).unwrap()));
+        self.table = Some(Box::new(deferred_arg(table)));
 
         self
     }
@@ -83,7 +118,7 @@ impl<'a> SelectQuery<'a> {
     }
 
     pub fn order_by(&mut self, ordering: impl ordering::TryIntoOrdering<'a>) -> &mut Self {
-        let order = ordering.try_into_ordering().unwrap();
+        let order = ordering::deferred_ordering(ordering);
 
         if let Some(ordering) = &mut self.ordering {
             ordering.push(order);
@@ -97,17 +132,17 @@ impl<'a> SelectQuery<'a> {
     fn join_internal<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
         &mut self,
         join_type: Option<&'static str>,
-        table: &'a str,
+        table: impl Into<Cow<'a, str>>,
         left: L,
-        op: &'a str,
+        op: impl Into<Cow<'a, str>>,
         right: R,
     ) {
         let join = join::Join::Regular(join::RegularJoin {
             join_type,
-            table: Cow::Borrowed(table),
-            left: left.try_into_arg().unwrap(),
-            op: Cow::Borrowed(op),
-            right: right.try_into_arg().unwrap(),
+            table: table.into(),
+            left: deferred_arg(left),
+            op: op.into(),
+            right: deferred_arg(right),
         });
 
         if let Some(joins) = &mut self.joins {
@@ -119,9 +154,9 @@ impl<'a> SelectQuery<'a> {
 
     pub fn join<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
         &mut self,
-        table: &'a str,
+        table: impl Into<Cow<'a, str>>,
         left: L,
-        op: &'a str,
+        op: impl Into<Cow<'a, str>>,
         right: R,
     ) -> &mut Self {
         self.join_internal(None, table, left, op, right);
@@ -131,9 +166,9 @@ impl<'a> SelectQuery<'a> {
 
     pub fn left_join<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
         &mut self,
-        table: &'a str,
+        table: impl Into<Cow<'a, str>>,
         left: L,
-        op: &'a str,
+        op: impl Into<Cow<'a, str>>,
         right: R,
     ) -> &mut Self {
         self.join_internal(Some("left"), table, left, op, right);
@@ -143,9 +178,9 @@ impl<'a> SelectQuery<'a> {
 
     pub fn right_join<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
         &mut self,
-        table: &'a str,
+        table: impl Into<Cow<'a, str>>,
         left: L,
-        op: &'a str,
+        op: impl Into<Cow<'a, str>>,
         right: R,
     ) -> &mut Self {
         self.join_internal(Some("right"), table, left, op, right);
@@ -155,9 +190,9 @@ impl<'a> SelectQuery<'a> {
 
     pub fn inner_join<L: TryIntoArg<'a>, R: TryIntoArg<'a>>(
         &mut self,
-        table: &'a str,
+        table: impl Into<Cow<'a, str>>,
         left: L,
-        op: &'a str,
+        op: impl Into<Cow<'a, str>>,
         right: R,
     ) -> &mut Self {
         self.join_internal(Some("inner"), table, left, op, right);
@@ -165,14 +200,14 @@ impl<'a> SelectQuery<'a> {
         self
     }
 
-    pub fn alias(&mut self, alias: &'a str) -> &mut Self {
-        self.alias = Some(Cow::Borrowed(alias));
+    pub fn alias(&mut self, alias: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.alias = Some(alias.into());
 
         self
     }
 
     pub fn group_by(&mut self, group: impl TryIntoArg<'a>) -> &mut Self {
-        let arg = group.try_into_arg().unwrap();
+        let arg = deferred_arg(group);
 
         if let Some(group_by) = &mut self.group_by {
             group_by.push(arg);
@@ -183,20 +218,70 @@ impl<'a> SelectQuery<'a> {
         self
     }
 
+    /// Derives `select count(*)` with the same source and filters, dropping
+    /// ordering, limit and offset. Grouped queries are counted as a sub query.
+    pub fn to_count_query(&self) -> SelectQuery<'a> {
+        let mut query = self.clone();
+        query.ordering = None;
+        query.limit = None;
+        query.offset = None;
+        query.alias = None;
+
+        if query.group_by.is_some() {
+            query.alias("t");
+
+            let mut count = SelectQuery::default();
+            count.push_column(Raw::new("count(*)")).from(query);
+
+            count
+        } else {
+            query.columns(vec![Raw::new("count(*)")]);
+
+            query
+        }
+    }
+
     // todo: pub fn column(&mut self, column: &str)
 
-    pub fn sql<D>(&'a self) -> Sql<'a>
+    /// Renders the query, failing on a raw fragment which doesn't match its
+    /// bindings.
+    pub fn try_sql<'s, D>(&'s self) -> Result<Sql<'s, D::Database>, Error>
     where
-        D: SqlDialect<'a>,
+        D: SqlDialect<'s>,
     {
         let mut builder = D::init();
 
         builder.build_select(self);
 
-        builder.sql()
+        builder.try_sql()
+    }
+
+    /// Renders the query.
+    ///
+    /// # Panics
+    ///
+    /// On a raw fragment which doesn't match its bindings, see [`Self::try_sql`].
+    pub fn sql<'s, D>(&'s self) -> Sql<'s, D::Database>
+    where
+        D: SqlDialect<'s>,
+    {
+        self.try_sql::<D>().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Renders into [`Sql`] owning its bindings, so the query stays borrowed
+    /// only for the duration of the call.
+    pub fn to_sql<'s, D>(&'s self) -> Sql<'static, D::Database>
+    where
+        D: SqlDialect<'s>,
+    {
+        self.sql::<D>().into_owned()
     }
 
-    pub fn sqlx_qb<D: SqlDialect<'a>>(&'a self) -> D::SqlxQb {
+    #[cfg(feature = "sqlx")]
+    pub fn sqlx_qb<'s, D>(&'s self) -> Result<D::SqlxQb, Error>
+    where
+        D: SqlxDialect<'s>,
+    {
         let mut builder = D::init();
 
         builder.build_select(self);
@@ -1,4 +1,4 @@
-use crate::query_builder::{Arg, SqlKeyword, TryIntoArg};
+use crate::query_builder::{deferred_arg, raw::Raw, Arg, SqlKeyword, TryIntoArg};
 
 #[derive(Debug, Clone)]
 pub struct Ordering<'a> {
@@ -8,15 +8,34 @@ pub struct Ordering<'a> {
     pub null_first: Option<bool>,
 }
 
+impl<'a> Ordering<'a> {
+    pub fn into_owned(self) -> Ordering<'static> {
+        Ordering {
+            left: self.left.into_owned(),
+            right: self.right.into_owned(),
+            null_first: self.null_first,
+        }
+    }
+}
+
 pub trait TryIntoOrdering<'a> {
     fn try_into_ordering(self) -> Result<Ordering<'a>, ()>;
 }
 
+// a failed conversion is reported when the query is rendered
+pub(crate) fn deferred_ordering<'a>(ordering: impl TryIntoOrdering<'a>) -> Ordering<'a> {
+    ordering.try_into_ordering().unwrap_or_else(|()| Ordering {
+        left: Arg::Raw(Raw::invalid("can't be converted into an ordering")),
+        right: Arg::Keyword(SqlKeyword::Asc),
+        null_first: None,
+    })
+}
+
 impl<'a, T1: TryIntoArg<'a>, T2: TryIntoArg<'a>> TryIntoOrdering<'a> for (T1, T2) {
     fn try_into_ordering(self) -> Result<Ordering<'a>, ()> {
         Ok(Ordering {
-            left: self.0.try_into_arg().unwrap(),
-            right: self.1.try_into_arg().unwrap(),
+            left: deferred_arg(self.0),
+            right: deferred_arg(self.1),
             null_first: None,
         })
     }
@@ -25,8 +44,8 @@ impl<'a, T1: TryIntoArg<'a>, T2: TryIntoArg<'a>> TryIntoOrdering<'a> for (T1, T2
 impl<'a, T1: TryIntoArg<'a>, T2: TryIntoArg<'a>> TryIntoOrdering<'a> for (T1, T2, SqlKeyword) {
     fn try_into_ordering(self) -> Result<Ordering<'a>, ()> {
         Ok(Ordering {
-            left: self.0.try_into_arg().unwrap(),
-            right: self.1.try_into_arg().unwrap(),
+            left: deferred_arg(self.0),
+            right: deferred_arg(self.1),
             null_first: Some(matches!(self.2, SqlKeyword::NullsFirst)),
         })
     }
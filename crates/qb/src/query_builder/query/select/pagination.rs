@@ -0,0 +1,477 @@
+use super::{ordering::Ordering, SelectQuery};
+use crate::{
+    error::{Error, ErrorKind},
+    query_builder::{
+        conditions::{ConditionOp, GroupedWhereCondition, SingleWhereCondition, WhereCondition},
+        raw::Raw,
+        Arg, ArgValue, PushCondition, Row, RowBuilder, SqlKeyword, Value,
+    },
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use std::borrow::Cow;
+
+/// Position after the last row of a page: values of its `order by` columns.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    pub values: Vec<Value<'a>>,
+}
+
+fn invalid_cursor(reason: impl Into<String>) -> Error {
+    ErrorKind::InvalidCursor(reason.into()).into()
+}
+
+fn is_desc(ordering: &Ordering) -> bool {
+    match &ordering.right {
+        Arg::Keyword(keyword) => matches!(keyword, SqlKeyword::Desc),
+        Arg::Raw(raw) => raw.sql.trim().eq_ignore_ascii_case("desc"),
+        _ => false,
+    }
+}
+
+// `users.id` in `order by` matches the `id` column of a row
+fn column_name<'o>(ordering: &'o Ordering) -> Option<&'o str> {
+    match &ordering.left {
+        Arg::Relation(relation) => relation.0.rsplit('.').next(),
+        Arg::Identifier(identifier) => Some(&identifier.0),
+        _ => None,
+    }
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(values: impl IntoIterator<Item = Value<'a>>) -> Self {
+        Self {
+            values: values.into_iter().collect(),
+        }
+    }
+
+    /// Takes the values of the query's `order by` columns from the last row
+    /// of a page.
+    pub fn from_row<R: Row<'a>>(query: &SelectQuery, row: R) -> Result<Self, Error> {
+        let mut builder = RowBuilder::default();
+        row.into_row(&mut builder);
+
+        let mut values = builder.values.into_iter().map(Some).collect::<Vec<_>>();
+        let columns = R::columns();
+
+        query
+            .ordering
+            .iter()
+            .flatten()
+            .map(|ordering| {
+                let name = column_name(ordering)
+                    .ok_or_else(|| invalid_cursor("order by is not a column"))?;
+
+                columns
+                    .iter()
+                    .position(|column| *column == name)
+                    .and_then(|idx| values[idx].take())
+                    .ok_or_else(|| ErrorKind::UnknownField(name.to_owned()).into())
+            })
+            .collect::<Result<Vec<_>, Error>>()
+            .map(Self::new)
+    }
+
+    pub fn into_owned(self) -> Cursor<'static> {
+        Cursor::new(self.values.into_iter().map(Value::into_owned))
+    }
+
+    /// Url safe string to hand out to clients.
+    pub fn encode(&self) -> Result<String, Error> {
+        let mut buf = vec![];
+
+        for value in &self.values {
+            encode_value(&mut buf, value)?;
+        }
+
+        Ok(URL_SAFE_NO_PAD.encode(buf))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Cursor<'static>, Error> {
+        let buf = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|err| invalid_cursor(err.to_string()))?;
+        let mut reader = Reader { buf: &buf, pos: 0 };
+        let mut values = vec![];
+
+        while reader.pos < buf.len() {
+            values.push(reader.value()?);
+        }
+
+        Ok(Cursor { values })
+    }
+}
+
+fn encode_len(buf: &mut Vec<u8>, len: usize) {
+    buf.extend((len as u32).to_le_bytes());
+}
+
+fn encode_value(buf: &mut Vec<u8>, value: &Value) -> Result<(), Error> {
+    match value {
+        Value::Null | Value::TypedNull(_) => buf.push(b'n'),
+        Value::String(value) => {
+            buf.push(b's');
+            encode_len(buf, value.len());
+            buf.extend(value.as_bytes());
+        }
+        Value::SmallInt(value) => {
+            buf.push(b'h');
+            buf.extend(value.to_le_bytes());
+        }
+        Value::Integer(value) => {
+            buf.push(b'i');
+            buf.extend(value.to_le_bytes());
+        }
+        Value::BigInt(value) => {
+            buf.push(b'l');
+            buf.extend(value.to_le_bytes());
+        }
+        Value::Real(value) => {
+            buf.push(b'f');
+            buf.extend(value.to_le_bytes());
+        }
+        Value::Double(value) => {
+            buf.push(b'd');
+            buf.extend(value.to_le_bytes());
+        }
+        Value::Boolean(value) => buf.extend([b'b', *value as u8]),
+        Value::Bytes(value) => {
+            buf.push(b'y');
+            encode_len(buf, value.len());
+            buf.extend(value.iter());
+        }
+        #[cfg(feature = "rust_decimal")]
+        Value::Decimal(value) => {
+            let value = value.to_string();
+            buf.push(b'm');
+            encode_len(buf, value.len());
+            buf.extend(value.as_bytes());
+        }
+        #[cfg(feature = "uuid")]
+        Value::Uuid(value) => {
+            buf.push(b'u');
+            buf.extend(value.as_bytes());
+        }
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeUtc(value) => {
+            buf.push(b'c');
+            buf.extend(value.timestamp().to_le_bytes());
+            buf.extend(value.timestamp_subsec_nanos().to_le_bytes());
+        }
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTime(value) => {
+            use chrono::TimeZone;
+
+            let value = chrono::Utc.from_utc_datetime(value);
+            buf.push(b'C');
+            buf.extend(value.timestamp().to_le_bytes());
+            buf.extend(value.timestamp_subsec_nanos().to_le_bytes());
+        }
+        #[cfg(feature = "time")]
+        Value::TimeDateTimeTz(value) => {
+            buf.push(b't');
+            buf.extend(value.unix_timestamp_nanos().to_le_bytes());
+        }
+        #[cfg(feature = "time")]
+        Value::TimeDateTime(value) => {
+            buf.push(b'T');
+            buf.extend(value.assume_utc().unix_timestamp_nanos().to_le_bytes());
+        }
+        value => {
+            return Err(invalid_cursor(format!(
+                "{:?} can't be stored in a cursor",
+                value
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+struct Reader<'b> {
+    buf: &'b [u8],
+    pos: usize,
+}
+
+impl<'b> Reader<'b> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let bytes = self.bytes(N)?;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'b [u8], Error> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid_cursor("unexpected end"))?;
+        self.pos += len;
+
+        Ok(bytes)
+    }
+
+    fn sized(&mut self) -> Result<&'b [u8], Error> {
+        let len = u32::from_le_bytes(self.take()?) as usize;
+
+        self.bytes(len)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        String::from_utf8(self.sized()?.to_vec()).map_err(|err| invalid_cursor(err.to_string()))
+    }
+
+    fn value(&mut self) -> Result<Value<'static>, Error> {
+        let [tag] = self.take()?;
+
+        let value = match tag {
+            b'n' => Value::Null,
+            b's' => Value::String(Cow::Owned(self.string()?)),
+            b'h' => Value::SmallInt(i16::from_le_bytes(self.take()?)),
+            b'i' => Value::Integer(i32::from_le_bytes(self.take()?)),
+            b'l' => Value::BigInt(i64::from_le_bytes(self.take()?)),
+            b'f' => Value::Real(f32::from_le_bytes(self.take()?)),
+            b'd' => Value::Double(f64::from_le_bytes(self.take()?)),
+            b'b' => Value::Boolean(self.take::<1>()? != [0]),
+            b'y' => Value::Bytes(Cow::Owned(self.sized()?.to_vec())),
+            #[cfg(feature = "rust_decimal")]
+            b'm' => Value::Decimal(
+                self.string()?
+                    .parse()
+                    .map_err(|err: rust_decimal::Error| invalid_cursor(err.to_string()))?,
+            ),
+            #[cfg(feature = "uuid")]
+            b'u' => Value::Uuid(uuid::Uuid::from_bytes(self.take()?)),
+            #[cfg(feature = "chrono")]
+            b'c' | b'C' => {
+                use chrono::TimeZone;
+
+                let secs = i64::from_le_bytes(self.take()?);
+                let nanos = u32::from_le_bytes(self.take()?);
+                let value = chrono::Utc
+                    .timestamp_opt(secs, nanos)
+                    .single()
+                    .ok_or_else(|| invalid_cursor("timestamp is out of range"))?;
+
+                if tag == b'c' {
+                    Value::ChronoDateTimeUtc(value)
+                } else {
+                    Value::ChronoDateTime(value.naive_utc())
+                }
+            }
+            #[cfg(feature = "time")]
+            b't' | b'T' => {
+                let value = time::OffsetDateTime::from_unix_timestamp_nanos(i128::from_le_bytes(
+                    self.take()?,
+                ))
+                .map_err(|err| invalid_cursor(err.to_string()))?;
+
+                if tag == b't' {
+                    Value::TimeDateTimeTz(value)
+                } else {
+                    Value::TimeDateTime(time::PrimitiveDateTime::new(value.date(), value.time()))
+                }
+            }
+            tag => return Err(invalid_cursor(format!("unknown value tag {}", tag))),
+        };
+
+        Ok(value)
+    }
+}
+
+fn compare<'a>(op: ConditionOp, ordering: &Ordering<'a>, value: Value<'a>) -> WhereCondition<'a> {
+    WhereCondition::Single(SingleWhereCondition {
+        op,
+        left: ordering.left.clone(),
+        middle: Cow::Borrowed(if is_desc(ordering) { "<" } else { ">" }),
+        right: Arg::Value(ArgValue::Value(value)),
+    })
+}
+
+// `(a, b) > ($1, $2)`
+fn row_values_condition<'a>(
+    orderings: &[Ordering<'a>],
+    values: Vec<Value<'a>>,
+) -> WhereCondition<'a> {
+    let names = (0..orderings.len())
+        .map(|idx| format!("{{c{}}}", idx))
+        .collect::<Vec<_>>();
+    let placeholders = vec!["?"; orderings.len()];
+
+    let mut left = Raw::new(format!("({})", names.join(", ")));
+    left.fragments = Some(
+        orderings
+            .iter()
+            .enumerate()
+            .map(|(idx, ordering)| (Cow::Owned(format!("c{}", idx)), ordering.left.clone()))
+            .collect(),
+    );
+
+    WhereCondition::Single(SingleWhereCondition {
+        op: ConditionOp::And,
+        left: Arg::Raw(left),
+        middle: Cow::Borrowed(if is_desc(&orderings[0]) { "<" } else { ">" }),
+        right: Arg::Raw(Raw::new(format!("({})", placeholders.join(", "))).bindings(values)),
+    })
+}
+
+// `(a > $1) or (a = $2 and b < $3)`
+fn expanded_condition<'a>(
+    orderings: &[Ordering<'a>],
+    values: Vec<Value<'a>>,
+) -> WhereCondition<'a> {
+    let mut condition = GroupedWhereCondition::new(ConditionOp::And);
+
+    for idx in 0..orderings.len() {
+        let mut branch = GroupedWhereCondition::new(ConditionOp::Or);
+
+        for (ordering, value) in orderings.iter().zip(&values).take(idx) {
+            branch
+                .conditions
+                .push(WhereCondition::Single(SingleWhereCondition {
+                    op: ConditionOp::And,
+                    left: ordering.left.clone(),
+                    middle: Cow::Borrowed("="),
+                    right: Arg::Value(ArgValue::Value(value.clone())),
+                }));
+        }
+
+        branch.conditions.push(compare(
+            ConditionOp::And,
+            &orderings[idx],
+            values[idx].clone(),
+        ));
+        condition.conditions.push(WhereCondition::Group(branch));
+    }
+
+    WhereCondition::Group(condition)
+}
+
+impl<'a> SelectQuery<'a> {
+    fn keyset(&mut self, cursor: Cursor<'a>, row_values: bool) -> Result<&mut Self, Error> {
+        let orderings = self.ordering.clone().unwrap_or_default();
+
+        if orderings.is_empty() || orderings.len() != cursor.values.len() {
+            return Err(invalid_cursor(format!(
+                "cursor has {} values for {} order by columns",
+                cursor.values.len(),
+                orderings.len()
+            )));
+        }
+
+        // `null` compares to nothing, so rows around it would be skipped
+        if orderings
+            .iter()
+            .any(|ordering| ordering.null_first.is_some())
+        {
+            return Err(invalid_cursor("order by columns must not be nullable"));
+        }
+
+        if let Some(idx) = cursor
+            .values
+            .iter()
+            .position(|value| matches!(value, Value::Null | Value::TypedNull(_)))
+        {
+            return Err(invalid_cursor(format!("cursor value {} is null", idx)));
+        }
+
+        let same_direction = orderings
+            .iter()
+            .all(|ordering| is_desc(ordering) == is_desc(&orderings[0]));
+
+        let condition = if row_values && same_direction {
+            row_values_condition(&orderings, cursor.values)
+        } else {
+            expanded_condition(&orderings, cursor.values)
+        };
+
+        // keep `a or b` of the query from swallowing the keyset condition
+        let conditions = std::mem::take(&mut self.where_);
+        if !conditions.is_empty() {
+            self.push_cond(WhereCondition::Group(GroupedWhereCondition {
+                op: ConditionOp::And,
+                conditions,
+            }));
+        }
+
+        self.push_cond(condition);
+
+        Ok(self)
+    }
+
+    /// Keyset pagination: selects rows following `cursor` in the current
+    /// `order by`, as `(a, b) > ($1, $2)`. Mixed directions fall back to the
+    /// expanded form. The `order by` columns must not be nullable.
+    pub fn after(&mut self, cursor: Cursor<'a>) -> Result<&mut Self, Error> {
+        self.keyset(cursor, true)
+    }
+
+    /// Same as [`after`](Self::after), but always expands the comparison into
+    /// `a > $1 or (a = $2 and b > $3)` for dialects without row values.
+    pub fn after_expanded(&mut self, cursor: Cursor<'a>) -> Result<&mut Self, Error> {
+        self.keyset(cursor, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cursor;
+    use crate::{
+        error::ErrorKind,
+        query_builder::{SqlKeyword, Value},
+        QueryBuilder, Row, ValueExt,
+    };
+
+    #[test]
+    fn encode_decode() {
+        let cursor = Cursor::new(["a?b".value(), 42i64.value(), true.value(), Value::Null]);
+        let decoded = Cursor::decode(&cursor.encode().unwrap()).unwrap();
+
+        assert!(matches!(
+            decoded.values[..],
+            [
+                Value::String(ref s),
+                Value::BigInt(42),
+                Value::Boolean(true),
+                Value::Null
+            ] if s == "a?b"
+        ));
+    }
+
+    #[test]
+    fn decode_garbage() {
+        assert!(matches!(
+            Cursor::decode("AAAA").unwrap_err().kind(),
+            ErrorKind::InvalidCursor(_)
+        ));
+        assert!(matches!(
+            Cursor::decode("not base64!").unwrap_err().kind(),
+            ErrorKind::InvalidCursor(_)
+        ));
+    }
+
+    #[derive(Row)]
+    struct Post {
+        id: i64,
+        title: String,
+        rating: i32,
+    }
+
+    #[test]
+    fn from_row() {
+        let mut qb = QueryBuilder::select();
+        qb.from("posts")
+            .order_by(("posts.rating", SqlKeyword::Desc))
+            .order_by(("id", SqlKeyword::Asc));
+
+        let post = Post {
+            id: 7,
+            title: "hello".to_owned(),
+            rating: 3,
+        };
+        let cursor = Cursor::from_row(&qb, post).unwrap();
+
+        assert!(matches!(
+            cursor.values[..],
+            [Value::Integer(3), Value::BigInt(7)]
+        ));
+    }
+}
@@ -1,8 +1,58 @@
 use super::value::Value;
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
 
-pub trait Row<'a> {
+pub trait Row<'a>: Sized {
     fn columns() -> &'static [&'static str];
     fn into_row(self, builder: &mut RowBuilder<'a>);
+
+    /// Columns written by inserts, without database generated ones.
+    fn insert_columns() -> &'static [&'static str] {
+        Self::columns()
+    }
+
+    fn into_insert_row(self, builder: &mut RowBuilder<'a>) {
+        self.into_row(builder)
+    }
+
+    /// Table used by inserts when it's not set explicitly.
+    fn table() -> Option<&'static str> {
+        None
+    }
+}
+
+/// Column lists of derived rows which flatten a generic row, built and leaked
+/// once per row type, `key` is the type and the listing method.
+#[doc(hidden)]
+pub fn intern_columns(
+    key: (TypeId, &'static str),
+    build: impl FnOnce() -> Vec<&'static str>,
+) -> &'static [&'static str] {
+    type Interned = HashMap<(TypeId, &'static str), &'static [&'static str]>;
+
+    static INTERNED: OnceLock<RwLock<Interned>> = OnceLock::new();
+
+    let interned = INTERNED.get_or_init(Default::default);
+
+    if let Some(columns) = interned
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+    {
+        return columns;
+    }
+
+    // built without the lock, flattened generic rows intern their own lists
+    let columns = build();
+
+    interned
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(key)
+        .or_insert_with(|| Vec::leak(columns))
 }
 
 #[derive(Debug, Default)]
@@ -15,3 +65,26 @@ impl<'a> RowBuilder<'a> {
         self.values.push(value);
     }
 }
+
+/// Partial update, sets only the columns which are present.
+pub trait Patch<'a>: Sized {
+    fn into_patch(self, builder: &mut PatchBuilder<'a>);
+
+    /// Table used by updates when it's not set explicitly.
+    fn table() -> Option<&'static str> {
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PatchBuilder<'a> {
+    pub columns: Vec<&'static str>,
+    pub values: Vec<Value<'a>>,
+}
+
+impl<'a> PatchBuilder<'a> {
+    pub fn set(&mut self, column: &'static str, value: Value<'a>) {
+        self.columns.push(column);
+        self.values.push(value);
+    }
+}
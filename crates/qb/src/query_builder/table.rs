@@ -0,0 +1,271 @@
+#[cfg(feature = "json")]
+use super::Json;
+use super::{
+    conditions::{ConditionOp, SingleWhereCondition, WhereCondition},
+    raw::Raw,
+    select::column::{Column, TryIntoColumn},
+    Arg, ArgValue, Relation, TryIntoArg, Value, ValueExt,
+};
+use std::{borrow::Cow, marker::PhantomData};
+
+pub trait Table {
+    const TABLE: &'static str;
+}
+
+/// Column handle generated by `#[derive(Table)]`, `T` is the Rust type of the
+/// column values.
+pub struct TableColumn<T> {
+    pub table: &'static str,
+    pub name: &'static str,
+    /// `table.name`
+    pub qualified: &'static str,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> TableColumn<T> {
+    pub const fn new(table: &'static str, name: &'static str, qualified: &'static str) -> Self {
+        Self {
+            table,
+            name,
+            qualified,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn relation(&self) -> Relation<'static> {
+        Relation(Cow::Borrowed(self.qualified))
+    }
+
+    fn condition<'a>(self, middle: &'static str, right: Arg<'a>) -> WhereCondition<'a> {
+        WhereCondition::Single(SingleWhereCondition {
+            op: ConditionOp::And,
+            left: Arg::Relation(self.relation()),
+            middle: Cow::Borrowed(middle),
+            right,
+        })
+    }
+
+    pub fn eq<'a>(self, value: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition("=", value.into_arg())
+    }
+
+    pub fn ne<'a>(self, value: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition("<>", value.into_arg())
+    }
+
+    pub fn gt<'a>(self, value: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition(">", value.into_arg())
+    }
+
+    pub fn gte<'a>(self, value: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition(">=", value.into_arg())
+    }
+
+    pub fn lt<'a>(self, value: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition("<", value.into_arg())
+    }
+
+    pub fn lte<'a>(self, value: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition("<=", value.into_arg())
+    }
+
+    pub fn like<'a>(self, pattern: impl ColumnValue<'a, T>) -> WhereCondition<'a> {
+        self.condition("like", pattern.into_arg())
+    }
+
+    /// `column in ($1, $2, ...)`, no values give the always false `1 = 0`
+    pub fn in_<'a, V: ColumnLiteral<'a, T>>(
+        self,
+        values: impl IntoIterator<Item = V>,
+    ) -> WhereCondition<'a> {
+        let values = values
+            .into_iter()
+            .map(ColumnLiteral::into_value)
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return WhereCondition::Single(SingleWhereCondition {
+                op: ConditionOp::And,
+                left: Arg::Raw(Raw::new("1")),
+                middle: Cow::Borrowed("="),
+                right: Arg::Raw(Raw::new("0")),
+            });
+        }
+
+        self.condition("in", Arg::Value(ArgValue::Values(values)))
+    }
+
+    pub fn is_null<'a>(self) -> WhereCondition<'a> {
+        self.condition("is", Arg::Raw(Raw::new("null")))
+    }
+
+    pub fn is_not_null<'a>(self) -> WhereCondition<'a> {
+        self.condition("is not", Arg::Raw(Raw::new("null")))
+    }
+}
+
+impl<T> Clone for TableColumn<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TableColumn<T> {}
+
+impl<T> std::fmt::Debug for TableColumn<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TableColumn").field(&self.qualified).finish()
+    }
+}
+
+impl<'a, T> TryIntoArg<'a> for TableColumn<T> {
+    type E = crate::error::Error;
+
+    fn try_into_arg(self) -> Result<Arg<'a>, Self::E> {
+        Ok(Arg::Relation(self.relation()))
+    }
+}
+
+impl<'a, T> TryIntoColumn<'a> for TableColumn<T> {
+    fn try_into_column(self) -> Result<Column<'a>, ()> {
+        Ok(Column {
+            arg: Arg::Relation(self.relation()),
+            alias: None,
+        })
+    }
+}
+
+/// Value which can be compared with a column holding Rust type `T`.
+pub trait ColumnValue<'a, T> {
+    fn into_arg(self) -> Arg<'a>;
+}
+
+impl<'a, T> ColumnValue<'a, T> for TableColumn<T> {
+    fn into_arg(self) -> Arg<'a> {
+        Arg::Relation(self.relation())
+    }
+}
+
+/// Bound value, not a column, which fits a column holding Rust type `T`.
+pub trait ColumnLiteral<'a, T>: ColumnValue<'a, T> {
+    fn into_value(self) -> Value<'a>;
+}
+
+macro_rules! impl_column_value {
+    ($($column:ty => [$($value:ty),*]),* $(,)?) => {
+        $(
+            $(
+                impl_column_value!(@impl $column, $value);
+                impl_column_value!(@impl Option<$column>, $value);
+            )*
+        )*
+    };
+    (@impl $column:ty, $value:ty) => {
+        impl<'a> ColumnValue<'a, $column> for $value {
+            fn into_arg(self) -> Arg<'a> {
+                Arg::Value(ArgValue::Value(ValueExt::value(self)))
+            }
+        }
+
+        impl<'a> ColumnLiteral<'a, $column> for $value {
+            fn into_value(self) -> Value<'a> {
+                ValueExt::value(self)
+            }
+        }
+    };
+}
+
+impl_column_value! {
+    i16 => [i16],
+    i32 => [i32],
+    i64 => [i64],
+    f32 => [f32],
+    f64 => [f64],
+    bool => [bool],
+    String => [String, &'a str, Cow<'a, str>],
+    &'a str => [String, &'a str, Cow<'a, str>],
+    Cow<'a, str> => [String, &'a str, Cow<'a, str>],
+    Vec<u8> => [Vec<u8>, &'a [u8]],
+    &'a [u8] => [Vec<u8>, &'a [u8]],
+    Vec<i16> => [Vec<i16>, &'a [i16]],
+    Vec<i32> => [Vec<i32>, &'a [i32]],
+    Vec<i64> => [Vec<i64>, &'a [i64]],
+    Vec<f32> => [Vec<f32>, &'a [f32]],
+    Vec<f64> => [Vec<f64>, &'a [f64]],
+    Vec<bool> => [Vec<bool>, &'a [bool]],
+    Vec<String> => [Vec<String>, &'a [String], Vec<&'a str>, &'a [&'a str]],
+}
+
+#[cfg(feature = "rust_decimal")]
+impl_column_value! {
+    rust_decimal::Decimal => [rust_decimal::Decimal],
+}
+
+#[cfg(feature = "bigdecimal")]
+impl_column_value! {
+    bigdecimal::BigDecimal => [bigdecimal::BigDecimal],
+}
+
+#[cfg(feature = "chrono")]
+impl_column_value! {
+    chrono::NaiveDate => [chrono::NaiveDate],
+    chrono::NaiveTime => [chrono::NaiveTime],
+    chrono::NaiveDateTime => [chrono::NaiveDateTime],
+    chrono::DateTime<chrono::Utc> => [chrono::DateTime<chrono::Utc>],
+}
+
+#[cfg(feature = "time")]
+impl_column_value! {
+    time::Date => [time::Date],
+    time::Time => [time::Time],
+    time::PrimitiveDateTime => [time::PrimitiveDateTime],
+    time::OffsetDateTime => [time::OffsetDateTime],
+}
+
+#[cfg(feature = "uuid")]
+impl_column_value! {
+    uuid::Uuid => [uuid::Uuid],
+    Vec<uuid::Uuid> => [Vec<uuid::Uuid>, &'a [uuid::Uuid]],
+}
+
+#[cfg(feature = "json")]
+impl_column_value! {
+    serde_json::Value => [serde_json::Value],
+}
+
+// `Json<T>` columns, compared with the same wrapper or any json value; a
+// wrapper which can't be serialized is reported when the query is rendered,
+// so only json values are literals
+#[cfg(feature = "json")]
+macro_rules! impl_json_column_value {
+    ($($column:ty),* $(,)?) => {
+        $(
+            impl<'a, T: serde::Serialize> ColumnValue<'a, $column> for Json<T> {
+                fn into_arg(self) -> Arg<'a> {
+                    match self.try_value() {
+                        Ok(value) => Arg::Value(ArgValue::Value(value)),
+                        Err(err) => Arg::Raw(Raw::invalid(err)),
+                    }
+                }
+            }
+
+            impl<'a, T: serde::Serialize> ColumnValue<'a, $column> for serde_json::Value {
+                fn into_arg(self) -> Arg<'a> {
+                    Arg::Value(ArgValue::Value(ValueExt::value(self)))
+                }
+            }
+
+            impl<'a, T: serde::Serialize> ColumnLiteral<'a, $column> for serde_json::Value {
+                fn into_value(self) -> Value<'a> {
+                    ValueExt::value(self)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "json")]
+impl_json_column_value! {
+    Json<T>,
+    Option<Json<T>>,
+}
@@ -1,68 +1,477 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
 
 use super::{Arg, ArgValue, TryIntoArg};
+use crate::error::{Error, ErrorKind};
 
 #[derive(Debug, Clone)]
 pub enum Value<'a> {
     String(Cow<'a, str>),
+    SmallInt(i16),
     Integer(i32),
     BigInt(i64),
+    Real(f32),
+    Double(f64),
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
+    #[cfg(feature = "bigdecimal")]
+    BigDecimal(bigdecimal::BigDecimal),
     Boolean(bool),
+    #[cfg(feature = "chrono")]
+    ChronoDate(chrono::NaiveDate),
+    #[cfg(feature = "chrono")]
+    ChronoTime(chrono::NaiveTime),
+    #[cfg(feature = "chrono")]
+    ChronoDateTime(chrono::NaiveDateTime),
+    #[cfg(feature = "chrono")]
+    ChronoDateTimeUtc(chrono::DateTime<chrono::Utc>),
+    #[cfg(feature = "chrono")]
+    ChronoDuration(chrono::Duration),
+    #[cfg(feature = "time")]
+    TimeDate(time::Date),
+    #[cfg(feature = "time")]
+    TimeTime(time::Time),
+    #[cfg(feature = "time")]
+    TimeDateTime(time::PrimitiveDateTime),
+    #[cfg(feature = "time")]
+    TimeDateTimeTz(time::OffsetDateTime),
+    #[cfg(feature = "time")]
+    TimeDuration(time::Duration),
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    #[cfg(feature = "json")]
+    Json(serde_json::Value),
+    Bytes(Cow<'a, [u8]>),
+    Array(Array<'a>),
+    Custom(Arc<dyn CustomValue>),
     Null,
+    /// `NULL` which is bound with the type of the column, e.g. from `None::<i32>`
+    TypedNull(ValueType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    String,
+    SmallInt,
+    Integer,
+    BigInt,
+    Real,
+    Double,
+    #[cfg(feature = "rust_decimal")]
+    Decimal,
+    #[cfg(feature = "bigdecimal")]
+    BigDecimal,
+    Boolean,
+    #[cfg(feature = "chrono")]
+    ChronoDate,
+    #[cfg(feature = "chrono")]
+    ChronoTime,
+    #[cfg(feature = "chrono")]
+    ChronoDateTime,
+    #[cfg(feature = "chrono")]
+    ChronoDateTimeUtc,
+    #[cfg(feature = "chrono")]
+    ChronoDuration,
+    #[cfg(feature = "time")]
+    TimeDate,
+    #[cfg(feature = "time")]
+    TimeTime,
+    #[cfg(feature = "time")]
+    TimeDateTime,
+    #[cfg(feature = "time")]
+    TimeDateTimeTz,
+    #[cfg(feature = "time")]
+    TimeDuration,
+    #[cfg(feature = "uuid")]
+    Uuid,
+    #[cfg(feature = "json")]
+    Json,
+    Bytes,
+    Array(ArrayType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayType {
+    SmallInt,
+    Integer,
+    BigInt,
+    Real,
+    Double,
+    Boolean,
+    String,
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+/// User defined bindable type, e.g. a Postgres enum, `citext` or `ltree`.
+pub trait CustomValue: Debug + Send + Sync {
+    /// Type the binding is cast to, e.g. `$1::ltree` in Postgres
+    fn cast_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// Binds the value through `Ok(arg.bind(...))`, fails by default
+    #[cfg(feature = "sqlx")]
+    fn encode_postgres(
+        &self,
+        arg: crate::sql_dialect::postgres::PgArgument<'_>,
+    ) -> Result<crate::sql_dialect::postgres::PgEncoded, Error> {
+        let _ = arg;
+        Err(ErrorKind::UnsupportedValue(format!("{:?} can't be encoded by sqlx", self)).into())
+    }
+
+    /// Param for `tokio_postgres`, by default it fails when encoded
+    #[cfg(feature = "tokio-postgres")]
+    fn to_tokio_postgres(&self) -> Box<dyn tokio_postgres::types::ToSql + Sync> {
+        crate::tokio_pg::unsupported(format!("{:?} can't be encoded by tokio-postgres", self))
+    }
+
+    /// Param for `rusqlite`, fails by default
+    #[cfg(feature = "rusqlite")]
+    fn to_rusqlite(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Err(rusqlite::Error::ToSqlConversionFailure(Box::new(Error::from(
+            ErrorKind::UnsupportedValue(format!("{:?} can't be encoded by rusqlite", self)),
+        ))))
+    }
+}
+
+impl<'a> Value<'a> {
+    pub fn custom(value: impl CustomValue + 'static) -> Self {
+        Self::Custom(Arc::new(value))
+    }
+
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Self::String(v) => Value::String(Cow::Owned(v.into_owned())),
+            Self::SmallInt(v) => Value::SmallInt(v),
+            Self::Integer(v) => Value::Integer(v),
+            Self::BigInt(v) => Value::BigInt(v),
+            Self::Real(v) => Value::Real(v),
+            Self::Double(v) => Value::Double(v),
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(v) => Value::Decimal(v),
+            #[cfg(feature = "bigdecimal")]
+            Self::BigDecimal(v) => Value::BigDecimal(v),
+            Self::Boolean(v) => Value::Boolean(v),
+            #[cfg(feature = "chrono")]
+            Self::ChronoDate(v) => Value::ChronoDate(v),
+            #[cfg(feature = "chrono")]
+            Self::ChronoTime(v) => Value::ChronoTime(v),
+            #[cfg(feature = "chrono")]
+            Self::ChronoDateTime(v) => Value::ChronoDateTime(v),
+            #[cfg(feature = "chrono")]
+            Self::ChronoDateTimeUtc(v) => Value::ChronoDateTimeUtc(v),
+            #[cfg(feature = "chrono")]
+            Self::ChronoDuration(v) => Value::ChronoDuration(v),
+            #[cfg(feature = "time")]
+            Self::TimeDate(v) => Value::TimeDate(v),
+            #[cfg(feature = "time")]
+            Self::TimeTime(v) => Value::TimeTime(v),
+            #[cfg(feature = "time")]
+            Self::TimeDateTime(v) => Value::TimeDateTime(v),
+            #[cfg(feature = "time")]
+            Self::TimeDateTimeTz(v) => Value::TimeDateTimeTz(v),
+            #[cfg(feature = "time")]
+            Self::TimeDuration(v) => Value::TimeDuration(v),
+            #[cfg(feature = "uuid")]
+            Self::Uuid(v) => Value::Uuid(v),
+            #[cfg(feature = "json")]
+            Self::Json(v) => Value::Json(v),
+            Self::Bytes(v) => Value::Bytes(Cow::Owned(v.into_owned())),
+            Self::Array(v) => Value::Array(v.into_owned()),
+            Self::Custom(v) => Value::Custom(v),
+            Self::Null => Value::Null,
+            Self::TypedNull(v) => Value::TypedNull(v),
+        }
+    }
+}
+
+impl<'a> Array<'a> {
+    pub fn into_owned(self) -> Array<'static> {
+        match self {
+            Self::SmallInt(v) => Array::SmallInt(Cow::Owned(v.into_owned())),
+            Self::Integer(v) => Array::Integer(Cow::Owned(v.into_owned())),
+            Self::BigInt(v) => Array::BigInt(Cow::Owned(v.into_owned())),
+            Self::Real(v) => Array::Real(Cow::Owned(v.into_owned())),
+            Self::Double(v) => Array::Double(Cow::Owned(v.into_owned())),
+            Self::Boolean(v) => Array::Boolean(Cow::Owned(v.into_owned())),
+            Self::String(v) => {
+                Array::String(v.into_iter().map(|s| Cow::Owned(s.into_owned())).collect())
+            }
+            #[cfg(feature = "uuid")]
+            Self::Uuid(v) => Array::Uuid(Cow::Owned(v.into_owned())),
+        }
+    }
+}
+
+/// Homogeneous array which is bound as a single parameter.
+#[derive(Debug, Clone)]
+pub enum Array<'a> {
+    SmallInt(Cow<'a, [i16]>),
+    Integer(Cow<'a, [i32]>),
+    BigInt(Cow<'a, [i64]>),
+    Real(Cow<'a, [f32]>),
+    Double(Cow<'a, [f64]>),
+    Boolean(Cow<'a, [bool]>),
+    String(Vec<Cow<'a, str>>),
+    #[cfg(feature = "uuid")]
+    Uuid(Cow<'a, [uuid::Uuid]>),
+}
+
+pub trait ArrayElement<'a>: Clone + 'a {
+    const ARRAY_TYPE: ArrayType;
+
+    fn into_array(values: Cow<'a, [Self]>) -> Array<'a>;
+}
+
+macro_rules! impl_array_element {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl<'a> ArrayElement<'a> for $ty {
+                const ARRAY_TYPE: ArrayType = ArrayType::$variant;
+
+                fn into_array(values: Cow<'a, [Self]>) -> Array<'a> {
+                    Array::$variant(values)
+                }
+            }
+        )*
+    };
+}
+
+impl_array_element! {
+    i16 => SmallInt,
+    i32 => Integer,
+    i64 => BigInt,
+    f32 => Real,
+    f64 => Double,
+    bool => Boolean,
+}
+
+#[cfg(feature = "uuid")]
+impl_array_element! {
+    uuid::Uuid => Uuid,
+}
+
+impl<'a> ArrayElement<'a> for String {
+    const ARRAY_TYPE: ArrayType = ArrayType::String;
+
+    fn into_array(values: Cow<'a, [Self]>) -> Array<'a> {
+        Array::String(match values {
+            Cow::Borrowed(values) => values.iter().map(|s| Cow::Borrowed(s.as_str())).collect(),
+            Cow::Owned(values) => values.into_iter().map(Cow::Owned).collect(),
+        })
+    }
+}
+
+impl<'a> ArrayElement<'a> for &'a str {
+    const ARRAY_TYPE: ArrayType = ArrayType::String;
+
+    fn into_array(values: Cow<'a, [Self]>) -> Array<'a> {
+        Array::String(values.iter().map(|s| Cow::Borrowed(*s)).collect())
+    }
 }
 
 pub trait ValueExt<'a> {
+    /// Sql type used to bind `None::<Self>`, an untyped `NULL` when it is unknown
+    const VALUE_TYPE: Option<ValueType> = None;
+
     fn value(self) -> Value<'a>;
 }
 
-impl<'a> ValueExt<'a> for &'a str {
+macro_rules! impl_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl<'a> ValueExt<'a> for $ty {
+                const VALUE_TYPE: Option<ValueType> = Some(ValueType::$variant);
+
+                fn value(self) -> Value<'a> {
+                    Value::$variant(self.into())
+                }
+            }
+
+            impl<'a> From<$ty> for Value<'a> {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_value! {
+    i8 => SmallInt,
+    u8 => SmallInt,
+    i16 => SmallInt,
+    u16 => Integer,
+    i32 => Integer,
+    u32 => BigInt,
+    i64 => BigInt,
+    f32 => Real,
+    f64 => Double,
+    bool => Boolean,
+}
+
+#[cfg(feature = "rust_decimal")]
+impl_value! {
+    rust_decimal::Decimal => Decimal,
+}
+
+#[cfg(feature = "bigdecimal")]
+impl_value! {
+    bigdecimal::BigDecimal => BigDecimal,
+}
+
+#[cfg(feature = "chrono")]
+impl_value! {
+    chrono::NaiveDate => ChronoDate,
+    chrono::NaiveTime => ChronoTime,
+    chrono::NaiveDateTime => ChronoDateTime,
+    chrono::Duration => ChronoDuration,
+}
+
+// timestamps with any time zone are bound as `timestamptz` in UTC
+#[cfg(feature = "chrono")]
+impl<'a, Tz: chrono::TimeZone> ValueExt<'a> for chrono::DateTime<Tz> {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::ChronoDateTimeUtc);
+
     fn value(self) -> Value<'a> {
-        Value::String(Cow::Borrowed(self))
+        self.into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Value<'a> {
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        Self::ChronoDateTimeUtc(value.with_timezone(&chrono::Utc))
     }
 }
 
-impl<'a> ValueExt<'a> for i32 {
+#[cfg(feature = "time")]
+impl_value! {
+    time::Date => TimeDate,
+    time::Time => TimeTime,
+    time::PrimitiveDateTime => TimeDateTime,
+    time::OffsetDateTime => TimeDateTimeTz,
+    time::Duration => TimeDuration,
+}
+
+#[cfg(feature = "uuid")]
+impl_value! {
+    uuid::Uuid => Uuid,
+}
+
+#[cfg(feature = "json")]
+impl_value! {
+    serde_json::Value => Json,
+}
+
+/// Wrapper for binding any [`serde::Serialize`] type as json.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T: serde::Serialize> Json<T> {
+    pub fn try_value<'a>(&self) -> Result<Value<'a>, Error> {
+        serde_json::to_value(&self.0)
+            .map(Value::Json)
+            .map_err(|err| ErrorKind::Serialize(err.to_string()).into())
+    }
+}
+
+impl<'a, T: ArrayElement<'a>> ValueExt<'a> for Vec<T> {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::Array(T::ARRAY_TYPE));
+
     fn value(self) -> Value<'a> {
-        Value::Integer(self)
+        self.into()
     }
 }
 
-impl<'a> ValueExt<'a> for String {
+impl<'a, T: ArrayElement<'a>> ValueExt<'a> for &'a [T] {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::Array(T::ARRAY_TYPE));
+
     fn value(self) -> Value<'a> {
-        Value::String(Cow::Owned(self))
+        self.into()
+    }
+}
+
+impl<'a, T: ArrayElement<'a>> From<Vec<T>> for Value<'a> {
+    fn from(value: Vec<T>) -> Self {
+        Self::Array(T::into_array(Cow::Owned(value)))
+    }
+}
+
+impl<'a, T: ArrayElement<'a>> From<&'a [T]> for Value<'a> {
+    fn from(value: &'a [T]) -> Self {
+        Self::Array(T::into_array(Cow::Borrowed(value)))
     }
 }
 
-impl<'a> ValueExt<'a> for i64 {
+impl<'a> ValueExt<'a> for &'a [u8] {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::Bytes);
+
     fn value(self) -> Value<'a> {
-        Value::BigInt(self)
+        Value::Bytes(Cow::Borrowed(self))
     }
 }
 
-impl<'a> ValueExt<'a> for bool {
+impl<'a> ValueExt<'a> for Vec<u8> {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::Bytes);
+
     fn value(self) -> Value<'a> {
-        Value::Boolean(self)
+        Value::Bytes(Cow::Owned(self))
+    }
+}
+
+impl<'a> From<&'a [u8]> for Value<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self::Bytes(Cow::Borrowed(value))
     }
 }
 
-impl<'a, T: Into<Value<'a>>> ValueExt<'a> for Option<T> {
+impl<'a> From<Vec<u8>> for Value<'a> {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(Cow::Owned(value))
+    }
+}
+
+impl<'a> ValueExt<'a> for &'a str {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::String);
+
     fn value(self) -> Value<'a> {
-        match self {
-            Some(value) => value.into(),
-            None => Value::Null,
-        }
+        Value::String(Cow::Borrowed(self))
     }
 }
 
-impl<'a> From<i32> for Value<'a> {
-    fn from(value: i32) -> Self {
-        Self::Integer(value)
+impl<'a> ValueExt<'a> for Cow<'a, str> {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::String);
+
+    fn value(self) -> Value<'a> {
+        Value::String(self)
     }
 }
 
-impl<'a> From<i64> for Value<'a> {
-    fn from(value: i64) -> Self {
-        Self::BigInt(value)
+impl<'a> ValueExt<'a> for String {
+    const VALUE_TYPE: Option<ValueType> = Some(ValueType::String);
+
+    fn value(self) -> Value<'a> {
+        Value::String(Cow::Owned(self))
+    }
+}
+
+impl<'a> ValueExt<'a> for Value<'a> {
+    fn value(self) -> Value<'a> {
+        self
+    }
+}
+
+impl<'a, T: ValueExt<'a>> ValueExt<'a> for Option<T> {
+    const VALUE_TYPE: Option<ValueType> = T::VALUE_TYPE;
+
+    fn value(self) -> Value<'a> {
+        match self {
+            Some(value) => value.value(),
+            None => T::VALUE_TYPE.map_or(Value::Null, Value::TypedNull),
+        }
     }
 }
 
@@ -78,6 +487,13 @@ impl<'a> From<String> for Value<'a> {
     }
 }
 
+impl<'a> From<Cow<'a, str>> for Value<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        Self::String(value)
+    }
+}
+
+// the sql type is unknown here, use `ValueExt::value` to bind a typed `NULL`
 impl<'a, T: Into<Value<'a>>> From<Option<T>> for Value<'a> {
     fn from(value: Option<T>) -> Self {
         match value {
@@ -87,6 +503,26 @@ impl<'a, T: Into<Value<'a>>> From<Option<T>> for Value<'a> {
     }
 }
 
+// Postgres has no unsigned types, so `u64` only fits into `bigint` when it is
+// not larger than `i64::MAX`
+impl<'a> TryFrom<u64> for Value<'a> {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        i64::try_from(value)
+            .map(Value::BigInt)
+            .map_err(|_| ErrorKind::ValueOutOfRange(value.to_string()).into())
+    }
+}
+
+impl<'a> TryFrom<Option<u64>> for Value<'a> {
+    type Error = Error;
+
+    fn try_from(value: Option<u64>) -> Result<Self, Self::Error> {
+        value.map_or(Ok(Value::TypedNull(ValueType::BigInt)), Value::try_from)
+    }
+}
+
 impl<'a> TryIntoArg<'a> for Value<'a> {
     type E = crate::error::Error;
 
@@ -94,3 +530,179 @@ impl<'a> TryIntoArg<'a> for Value<'a> {
         Ok(Arg::Value(ArgValue::Value(self)))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Array, ArrayType, CustomValue, Value, ValueExt, ValueType};
+
+    #[test]
+    fn unsigned() {
+        assert!(matches!(200u8.value(), Value::SmallInt(200)));
+        assert!(matches!(u16::MAX.value(), Value::Integer(65535)));
+        assert!(matches!(u32::MAX.value(), Value::BigInt(4294967295)));
+        assert!(matches!(Value::try_from(10u64), Ok(Value::BigInt(10))));
+        assert!(Value::try_from(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn floats() {
+        assert!(matches!(1.5f32.value(), Value::Real(v) if v == 1.5));
+        assert!(matches!(Some(2.5f64).value(), Value::Double(v) if v == 2.5));
+    }
+
+    #[test]
+    fn bytes() {
+        let bytes = [1u8, 2, 3];
+
+        assert!(matches!(bytes.as_slice().value(), Value::Bytes(v) if *v == [1, 2, 3]));
+        assert!(matches!(vec![4u8].value(), Value::Bytes(v) if *v == [4]));
+    }
+
+    #[test]
+    fn typed_null() {
+        assert!(matches!(
+            None::<i32>.value(),
+            Value::TypedNull(ValueType::Integer)
+        ));
+        assert!(matches!(
+            None::<&str>.value(),
+            Value::TypedNull(ValueType::String)
+        ));
+        assert!(matches!(
+            None::<Vec<String>>.value(),
+            Value::TypedNull(ValueType::Array(ArrayType::String))
+        ));
+        assert!(matches!(
+            Some(None::<i64>).value(),
+            Value::TypedNull(ValueType::BigInt)
+        ));
+        assert!(matches!(
+            Value::try_from(None::<u64>),
+            Ok(Value::TypedNull(ValueType::BigInt))
+        ));
+    }
+
+    #[test]
+    fn untyped_null() {
+        struct Point(i32, i32);
+
+        impl From<Point> for Value<'_> {
+            fn from(value: Point) -> Self {
+                vec![value.0, value.1].into()
+            }
+        }
+
+        assert!(matches!(None::<Value>.value(), Value::Null));
+        assert!(matches!(Value::from(None::<Point>), Value::Null));
+        assert!(matches!(
+            Value::from(Some(Point(1, 2))),
+            Value::Array(Array::Integer(v)) if *v == [1, 2]
+        ));
+    }
+
+    #[test]
+    fn custom() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Mood(&'static str);
+
+        impl CustomValue for Mood {
+            fn cast_type(&self) -> Option<&str> {
+                Some("mood")
+            }
+
+            #[cfg(feature = "sqlx")]
+            fn encode_postgres(
+                &self,
+                arg: crate::sql_dialect::postgres::PgArgument<'_>,
+            ) -> Result<crate::sql_dialect::postgres::PgEncoded, crate::error::Error> {
+                Ok(arg.bind(self.0))
+            }
+
+            #[cfg(feature = "tokio-postgres")]
+            fn to_tokio_postgres(&self) -> Box<dyn tokio_postgres::types::ToSql + Sync> {
+                Box::new(self.0)
+            }
+
+            #[cfg(feature = "rusqlite")]
+            fn to_rusqlite(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+                rusqlite::ToSql::to_sql(self.0)
+            }
+        }
+
+        let value = Value::custom(Mood("happy"));
+
+        assert!(matches!(value, Value::Custom(v) if v.cast_type() == Some("mood")));
+    }
+
+    #[test]
+    fn arrays() {
+        let ids = [1, 2, 3];
+
+        assert!(matches!(
+            ids.as_slice().value(),
+            Value::Array(Array::Integer(v)) if *v == [1, 2, 3]
+        ));
+        assert!(matches!(
+            vec!["a".to_owned()].value(),
+            Value::Array(Array::String(v)) if v == ["a"]
+        ));
+        assert!(matches!(
+            Some(vec!["a", "b"]).value(),
+            Value::Array(Array::String(v)) if v == ["a", "b"]
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        use super::Json;
+        use std::collections::HashMap;
+
+        let map = HashMap::from([("key", 1)]);
+
+        assert!(matches!(
+            Json(map).try_value().unwrap(),
+            Value::Json(v) if v == serde_json::json!({ "key": 1 })
+        ));
+        assert!(Json(HashMap::from([((1, 2), 1)])).try_value().is_err());
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn decimal() {
+        let decimal = rust_decimal::Decimal::new(1050, 2);
+
+        assert!(matches!(decimal.value(), Value::Decimal(v) if v == decimal));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono() {
+        use chrono::{FixedOffset, TimeZone, Utc};
+
+        let date = chrono::NaiveDate::from_ymd_opt(2023, 9, 1).unwrap();
+        let offset_dt = FixedOffset::east_opt(3 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2023, 9, 1, 12, 0, 0)
+            .unwrap();
+
+        assert!(matches!(date.value(), Value::ChronoDate(v) if v == date));
+        assert!(matches!(
+            offset_dt.value(),
+            Value::ChronoDateTimeUtc(v) if v == Utc.with_ymd_and_hms(2023, 9, 1, 9, 0, 0).unwrap()
+        ));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time() {
+        let dt = time::OffsetDateTime::UNIX_EPOCH;
+
+        assert!(matches!(dt.value(), Value::TimeDateTimeTz(v) if v == dt));
+        assert!(matches!(
+            Some(time::Duration::HOUR).value(),
+            Value::TimeDuration(v) if v == time::Duration::HOUR
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    query_builder::Value,
+    sql_dialect::{sqlite::Sqlite, Sql},
+};
+use rusqlite::{
+    types::{ToSqlOutput, ValueRef},
+    Connection, Row, Statement, ToSql,
+};
+
+fn unsupported<T>(type_name: &str) -> rusqlite::Result<T> {
+    Err(rusqlite::Error::ToSqlConversionFailure(
+        format!("{type_name} is not supported by sqlite").into(),
+    ))
+}
+
+// rusqlite stores only `OffsetDateTime` from `time`, the rest is written as
+// ISO 8601 text like its chrono impls do
+#[cfg(feature = "time")]
+fn date_text(date: &time::Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+#[cfg(feature = "time")]
+fn time_text(time: &time::Time) -> String {
+    // fraction like chrono's `%.f`: none, millis, micros or nanos
+    let nanos = time.nanosecond();
+    let fraction = if nanos == 0 {
+        String::new()
+    } else if nanos.is_multiple_of(1_000_000) {
+        format!(".{:03}", nanos / 1_000_000)
+    } else if nanos.is_multiple_of(1_000) {
+        format!(".{:06}", nanos / 1_000)
+    } else {
+        format!(".{:09}", nanos)
+    };
+
+    format!(
+        "{:02}:{:02}:{:02}{}",
+        time.hour(),
+        time.minute(),
+        time.second(),
+        fraction
+    )
+}
+
+impl ToSql for Value<'_> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        match self {
+            Value::String(v) => Ok(ToSqlOutput::Borrowed(ValueRef::Text(v.as_bytes()))),
+            Value::SmallInt(v) => v.to_sql(),
+            Value::Integer(v) => v.to_sql(),
+            Value::BigInt(v) => v.to_sql(),
+            Value::Real(v) => v.to_sql(),
+            Value::Double(v) => v.to_sql(),
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(v) => Ok(ToSqlOutput::from(v.to_string())),
+            #[cfg(feature = "bigdecimal")]
+            Value::BigDecimal(v) => Ok(ToSqlOutput::from(v.to_string())),
+            Value::Boolean(v) => v.to_sql(),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDate(v) => v.to_sql(),
+            #[cfg(feature = "chrono")]
+            Value::ChronoTime(v) => v.to_sql(),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTime(v) => v.to_sql(),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeUtc(v) => v.to_sql(),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDuration(_) => unsupported("interval"),
+            #[cfg(feature = "time")]
+            Value::TimeDate(v) => Ok(ToSqlOutput::from(date_text(v))),
+            #[cfg(feature = "time")]
+            Value::TimeTime(v) => Ok(ToSqlOutput::from(time_text(v))),
+            #[cfg(feature = "time")]
+            Value::TimeDateTime(v) => Ok(ToSqlOutput::from(format!(
+                "{} {}",
+                date_text(&v.date()),
+                time_text(&v.time())
+            ))),
+            #[cfg(feature = "time")]
+            Value::TimeDateTimeTz(v) => v.to_sql(),
+            #[cfg(feature = "time")]
+            Value::TimeDuration(_) => unsupported("interval"),
+            #[cfg(feature = "uuid")]
+            Value::Uuid(v) => v.to_sql(),
+            #[cfg(feature = "json")]
+            Value::Json(v) => v.to_sql(),
+            Value::Bytes(v) => Ok(ToSqlOutput::Borrowed(ValueRef::Blob(v))),
+            Value::Array(_) => unsupported("array"),
+            Value::Custom(v) => v.to_rusqlite(),
+            Value::Null | Value::TypedNull(_) => Ok(ToSqlOutput::Borrowed(ValueRef::Null)),
+        }
+    }
+}
+
+impl<'a> Sql<'a, Sqlite> {
+    /// Prepares the statement on `conn` with all bindings bound.
+    pub fn prepare_rusqlite<'c>(&self, conn: &'c Connection) -> rusqlite::Result<Statement<'c>> {
+        let mut statement = conn.prepare(&self.sql)?;
+
+        for (idx, binding) in self.bindings.iter().enumerate() {
+            statement.raw_bind_parameter(idx + 1, binding.as_ref())?;
+        }
+
+        Ok(statement)
+    }
+
+    /// Runs the query, mapping every row with `f`.
+    pub fn query_rusqlite<T, F>(&self, conn: &Connection, f: F) -> rusqlite::Result<Vec<T>>
+    where
+        F: FnMut(&Row<'_>) -> rusqlite::Result<T>,
+    {
+        let mut statement = self.prepare_rusqlite(conn)?;
+        let rows = statement.raw_query().mapped(f).collect();
+
+        rows
+    }
+
+    /// Runs the query, returns the number of changed rows.
+    pub fn execute_rusqlite(&self, conn: &Connection) -> rusqlite::Result<usize> {
+        self.prepare_rusqlite(conn)?.raw_execute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{prelude::*, sql_dialect::sqlite::SqliteSqlDialect, CustomValue, Row, Value};
+    use rusqlite::Connection;
+
+    #[derive(Row)]
+    #[qb(table = "users")]
+    struct User {
+        name: String,
+        age: Option<i32>,
+        avatar: Vec<u8>,
+    }
+
+    #[test]
+    fn insert_and_query() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("create table users (name text, age integer, avatar blob)")
+            .unwrap();
+
+        let changed = QueryBuilder::insert()
+            .values([
+                User {
+                    name: "bob".into(),
+                    age: Some(30),
+                    avatar: vec![1, 2],
+                },
+                User {
+                    name: "alice".into(),
+                    age: None,
+                    avatar: vec![],
+                },
+            ])
+            .sql::<SqliteSqlDialect>()
+            .execute_rusqlite(&conn)
+            .unwrap();
+
+        assert_eq!(changed, 2);
+
+        let mut qb = QueryBuilder::select();
+        qb.columns(["name", "age", "avatar"])
+            .from("users")
+            .and_where(("name", "=", "bob".value()))
+            .or_where(("age", "is", Value::Null));
+
+        let rows = qb
+            .sql::<SqliteSqlDialect>()
+            .query_rusqlite(&conn, |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<i32>>(1)?,
+                    row.get::<_, Vec<u8>>(2)?,
+                ))
+            })
+            .unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                ("bob".to_owned(), Some(30), vec![1, 2]),
+                ("alice".to_owned(), None, vec![])
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_binding() {
+        let conn = Connection::open_in_memory().unwrap();
+        let mut qb = QueryBuilder::select();
+        qb.from("sqlite_master")
+            .and_where(("name", "=", vec![1, 2].value()));
+
+        assert!(qb
+            .sql::<SqliteSqlDialect>()
+            .prepare_rusqlite(&conn)
+            .is_err());
+
+        #[derive(Debug)]
+        struct Point;
+
+        impl CustomValue for Point {}
+
+        let mut qb = QueryBuilder::select();
+        qb.from("sqlite_master")
+            .and_where(("name", "=", Value::custom(Point)));
+
+        assert!(qb
+            .sql::<SqliteSqlDialect>()
+            .prepare_rusqlite(&conn)
+            .is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_as_text() {
+        use crate::RawExt;
+
+        let conn = Connection::open_in_memory().unwrap();
+        let date = time::Date::from_calendar_date(2023, time::Month::March, 4).unwrap();
+        let at = date.with_hms_milli(5, 6, 7, 80).unwrap();
+
+        let mut qb = QueryBuilder::select();
+        qb.push_column("?".raw().bindings(vec![at.value()]));
+
+        let text = qb
+            .sql::<SqliteSqlDialect>()
+            .query_rusqlite(&conn, |row| row.get::<_, String>(0))
+            .unwrap();
+
+        assert_eq!(text, vec!["2023-03-04 05:06:07.080"]);
+    }
+
+    #[cfg(all(feature = "chrono", feature = "time"))]
+    #[test]
+    fn time_text_matches_chrono() {
+        use rusqlite::ToSql;
+
+        for nanos in [0, 80_000_000, 123_000, 1, 999_999_999] {
+            let time_at = time::Date::from_calendar_date(2023, time::Month::March, 4)
+                .unwrap()
+                .with_hms_nano(5, 6, 7, nanos)
+                .unwrap();
+            let chrono_at = chrono::NaiveDate::from_ymd_opt(2023, 3, 4)
+                .unwrap()
+                .and_hms_nano_opt(5, 6, 7, nanos)
+                .unwrap();
+
+            assert_eq!(
+                time_at.value().to_sql().unwrap(),
+                chrono_at.value().to_sql().unwrap()
+            );
+            assert_eq!(
+                time_at.time().value().to_sql().unwrap(),
+                chrono_at.time().value().to_sql().unwrap()
+            );
+        }
+    }
+}
@@ -1,38 +1,86 @@
 pub mod postgres;
+pub mod sqlite;
+use std::{borrow::Cow, marker::PhantomData};
+
+use crate::error::Error;
 use crate::query_builder::{
-    raw::Raw, select::join::Join, Arg, ArgValue, ConditionOp, DeleteQuery, GroupedWhereCondition,
-    InsertQuery, InsertType, SelectQuery, SingleWhereCondition, SqlKeyword, UpdateQuery, Value,
-    WhereCondition,
+    raw::RawToken, select::join::Join, Arg, ArgValue, ConditionOp, DeleteQuery,
+    GroupedWhereCondition, InsertQuery, InsertType, SelectQuery, SingleWhereCondition, SqlKeyword,
+    UpdateQuery, Value, WhereCondition,
 };
 
 #[derive(Debug)]
 pub enum Dialect {
     Postgres,
+    Sqlite,
 }
 
-pub struct Sql<'a> {
+/// Rendered query, `DB` is the database it is written for, e.g.
+/// [`postgres::Postgres`], so it can't be run on another one.
+pub struct Sql<'a, DB> {
     pub sql: String,
-    pub bindings: Vec<&'a Value<'a>>,
+    pub bindings: Vec<Cow<'a, Value<'a>>>,
 
     // todo: remove it?
     pub dialect: Dialect,
+    pub database: PhantomData<fn() -> DB>,
 }
 
-pub trait SqlDialect<'a> {
-    const RELATION_QUOTE: char;
-    type SqlxQb;
+impl<'a, DB> Sql<'a, DB> {
+    /// Clones borrowed bindings, so the result can outlive the query builder.
+    pub fn into_owned(self) -> Sql<'static, DB> {
+        Sql {
+            sql: self.sql,
+            bindings: self
+                .bindings
+                .into_iter()
+                .map(|binding| Cow::Owned(binding.into_owned().into_owned()))
+                .collect(),
+            dialect: self.dialect,
+            database: PhantomData,
+        }
+    }
+}
 
+pub trait SqlDialect<'a>: Sized {
+    const RELATION_QUOTE: char;
+    /// Marker of the database, see [`Sql`]
+    type Database;
     fn init() -> Self;
     fn dialect() -> Dialect;
-    fn sql(self) -> Sql<'a>;
+    fn sql(self) -> Sql<'a, Self::Database>;
 
     fn write_str<S: AsRef<str>>(&mut self, sql: S);
     fn write_char(&mut self, ch: char);
     fn push_binding(&mut self, binding: &'a Value<'a>) -> usize;
     fn extend_bindings(&mut self, bindings: impl IntoIterator<Item = &'a Value<'a>>);
     fn get_bindings_count(&self) -> usize;
+    /// Keeps the first error found while rendering, e.g. a raw fragment
+    /// which doesn't match its bindings.
+    fn set_error(&mut self, error: Error);
+    fn take_error(&mut self) -> Option<Error>;
+
+    /// Rendered query, or the first error found while rendering it.
+    fn try_sql(mut self) -> Result<Sql<'a, Self::Database>, Error> {
+        match self.take_error() {
+            Some(error) => Err(error),
+            None => Ok(self.sql()),
+        }
+    }
+
+    fn write_identifier(&mut self, identifier: &str) {
+        self.write_char(Self::RELATION_QUOTE);
+
+        for ch in identifier.chars() {
+            if ch == Self::RELATION_QUOTE {
+                self.write_char(ch);
+            }
 
-    fn into_sqlx_qb(self) -> Self::SqlxQb;
+            self.write_char(ch);
+        }
+
+        self.write_char(Self::RELATION_QUOTE);
+    }
 
     fn write_relation(&mut self, relation: &str) {
         for (idx, relation_part) in relation.split('.').enumerate() {
@@ -43,9 +91,7 @@ pub trait SqlDialect<'a> {
             if relation_part == "*" {
                 self.write_char('*');
             } else {
-                self.write_char(Self::RELATION_QUOTE);
-                self.write_str(relation_part);
-                self.write_char(Self::RELATION_QUOTE);
+                self.write_identifier(relation_part);
             }
         }
     }
@@ -179,8 +225,6 @@ pub trait SqlDialect<'a> {
     }
 
     fn build_update(&mut self, qb: &'a UpdateQuery<'a>) {
-        self.extend_bindings(&qb.values);
-
         self.write_str("update");
 
         if let Some(table) = &qb.table {
@@ -197,8 +241,8 @@ pub trait SqlDialect<'a> {
 
             self.write_char(' ');
             self.write_relation(column);
-            self.write_str(" = $");
-            self.write_str((idx + 1).to_string().as_str());
+            self.write_str(" = ");
+            self.write_binding(&qb.values[idx]);
         });
 
         self.build_where(&qb.where_clause, 0);
@@ -225,7 +269,7 @@ pub trait SqlDialect<'a> {
                         self.write_char(' ');
                     }
 
-                    self.write_str(format!(r#""{}""#, column).as_str());
+                    self.write_identifier(column);
                 });
 
             self.write_char(')');
@@ -246,8 +290,6 @@ pub trait SqlDialect<'a> {
                 if !insert.bindings.is_empty() {
                     self.write_str(" values ");
 
-                    let mut binding_idx: usize = 1;
-
                     for (tuple_idx, values) in insert.bindings.chunks(columns_count).enumerate() {
                         if tuple_idx > 0 {
                             self.write_char(',');
@@ -256,22 +298,18 @@ pub trait SqlDialect<'a> {
 
                         self.write_char('(');
 
-                        for (idx, _) in values.iter().enumerate() {
+                        for (idx, value) in values.iter().enumerate() {
                             if idx > 0 {
                                 self.write_char(',');
                                 self.write_char(' ');
                             }
 
-                            self.write_char('$');
-                            self.write_str(binding_idx.to_string());
-                            binding_idx += 1;
+                            self.write_binding(value);
                         }
 
                         self.write_char(')');
                     }
                 }
-
-                self.extend_bindings(&insert.bindings);
             }
         };
     }
@@ -333,41 +371,82 @@ pub trait SqlDialect<'a> {
             });
     }
 
+    fn write_placeholder(&mut self, value: &'a Value<'a>) {
+        let idx = self.push_binding(value);
+        self.write_char('$');
+        self.write_str(idx.to_string());
+    }
+
+    fn write_binding(&mut self, value: &'a Value<'a>) {
+        match value {
+            // an untyped null has no type to bind it with
+            Value::Null => self.write_str("null"),
+            Value::Custom(custom) if custom.cast_type().is_some() => {
+                let cast_type = custom.cast_type().unwrap_or_default();
+                self.write_cast(cast_type, true, |dialect| dialect.write_placeholder(value));
+            }
+            _ => self.write_placeholder(value),
+        }
+    }
+
+    /// Writes `cast(<expr> as <type_name>)`. `simple` tells whether the
+    /// expression is a single term, for dialects with a postfix cast syntax.
+    fn write_cast<F>(&mut self, type_name: &str, _simple: bool, write_expr: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.write_str("cast(");
+        write_expr(self);
+        self.write_str(" as ");
+        self.write_str(type_name);
+        self.write_char(')');
+    }
+
     fn write_arg(&mut self, arg: &'a Arg<'a>) {
         match arg {
             Arg::Relation(rel) => self.write_relation(&rel.0),
-            Arg::Value(ArgValue::Value(Value::Null)) => {
+            Arg::Identifier(identifier) => self.write_identifier(&identifier.0),
+            Arg::Value(ArgValue::Value(Value::Null | Value::TypedNull(_))) => {
                 self.write_str("null");
             }
-            Arg::Value(ArgValue::Value(value)) => {
-                let idx = self.push_binding(value);
-                self.write_char('$');
-                self.write_str(idx.to_string().as_str());
-            }
+            Arg::Value(ArgValue::Value(value)) => self.write_binding(value),
             Arg::Value(ArgValue::Values(v)) => {
                 self.write_char('(');
-                v.iter().for_each(|value| {
-                    let idx = self.push_binding(value);
-                    self.write_char('$');
-                    self.write_str(idx.to_string().as_str());
+                v.iter().enumerate().for_each(|(idx, value)| {
+                    if idx > 0 {
+                        self.write_char(',');
+                        self.write_char(' ');
+                    }
+
+                    self.write_binding(value);
                 });
                 self.write_char(')');
             }
-            Arg::Raw(Raw { sql, bindings }) => {
-                let mut idx = self.get_bindings_count();
-
-                if let Some(bindings) = bindings {
-                    self.extend_bindings(bindings);
+            Arg::Raw(raw) => {
+                if let Err(error) = raw.validate() {
+                    self.set_error(error);
+                    return;
                 }
 
-                for ch in sql.chars() {
-                    if ch == '?' {
-                        idx += 1;
-                        self.write_char('$');
-                        self.write_str(idx.to_string());
-                    } else {
-                        self.write_char(ch);
-                    }
+                let mut positional = raw.bindings.iter().flatten();
+
+                for token in raw.tokens() {
+                    let value = match token {
+                        RawToken::Sql(sql) => {
+                            self.write_str(sql);
+                            continue;
+                        }
+                        RawToken::Fragment(name) => {
+                            self.write_arg(
+                                raw.fragment_arg(name).expect("raw fragment is missing"),
+                            );
+                            continue;
+                        }
+                        RawToken::Positional => positional.next(),
+                        RawToken::Named(name) => raw.named_binding(name),
+                    };
+
+                    self.write_binding(value.expect("raw binding is missing"));
                 }
             }
             Arg::SubQuery(sub_query) => {
@@ -380,6 +459,11 @@ pub trait SqlDialect<'a> {
                     self.write_relation(alias);
                 }
             }
+            Arg::Cast(cast) => {
+                self.write_cast(cast.type_name, cast.is_simple(), |dialect| {
+                    dialect.write_arg(&cast.arg)
+                });
+            }
             Arg::Keyword(keyword) => match keyword {
                 SqlKeyword::Asc => self.write_str("asc"),
                 SqlKeyword::Desc => self.write_str("desc"),
@@ -390,25 +474,37 @@ pub trait SqlDialect<'a> {
     }
 }
 
+/// Dialect of a database sqlx is built with, only these render into a
+/// [`sqlx::QueryBuilder`].
+#[cfg(feature = "sqlx")]
+pub trait SqlxDialect<'a>: SqlDialect<'a> {
+    type SqlxQb;
+
+    fn into_sqlx_qb(self) -> Result<Self::SqlxQb, crate::error::Error>;
+}
+
 #[cfg(test)]
 mod test {
     use super::SqlDialect;
     use crate::{
+        error::{Error, ErrorKind},
         prelude::*,
-        query_builder::{SqlKeyword, Value},
-        ColumnExt, RawExt,
+        query_builder::{Arg, CustomValue, SelectQuery, SqlKeyword, TryIntoArg, Value, ValueType},
+        AllowList, ColumnExt, Identifier, RawExt,
     };
+    use std::borrow::Cow;
 
     #[derive(Debug, Default)]
     pub struct TestDialect<'a> {
         pub sql: String,
         pub bindings: Vec<&'a Value<'a>>,
+        pub error: Option<Error>,
     }
 
     impl<'a> SqlDialect<'a> for TestDialect<'a> {
         const RELATION_QUOTE: char = '"';
 
-        type SqlxQb = ();
+        type Database = ();
 
         fn init() -> Self {
             Self::default()
@@ -418,16 +514,15 @@ mod test {
             super::Dialect::Postgres
         }
 
-        fn sql(self) -> super::Sql<'a> {
+        fn sql(self) -> super::Sql<'a, ()> {
             super::Sql {
                 sql: self.sql,
-                bindings: self.bindings,
+                bindings: self.bindings.into_iter().map(Cow::Borrowed).collect(),
                 dialect: Self::dialect(),
+                database: Default::default(),
             }
         }
 
-        fn into_sqlx_qb(self) -> Self::SqlxQb {}
-
         fn write_char(&mut self, ch: char) {
             self.sql.push(ch);
         }
@@ -448,6 +543,14 @@ mod test {
         fn get_bindings_count(&self) -> usize {
             self.bindings.len()
         }
+
+        fn set_error(&mut self, error: Error) {
+            self.error.get_or_insert(error);
+        }
+
+        fn take_error(&mut self) -> Option<Error> {
+            self.error.take()
+        }
     }
 
     #[test]
@@ -637,6 +740,107 @@ mod test {
         assert_eq!(sql.bindings.len(), 5);
     }
 
+    #[test]
+    fn untyped_null_is_written_as_literal() {
+        #[derive(unnamed_qb_macro::Row)]
+        struct TestRow {
+            a: i32,
+            b: Value<'static>,
+            c: Option<i32>,
+        }
+
+        let row = || TestRow {
+            a: 1,
+            b: Value::Null,
+            c: None,
+        };
+
+        let mut qb = QueryBuilder::update(row());
+        let sql = qb.table("my_tbl").sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"update "my_tbl" set "a" = $1, "b" = null, "c" = $2"#
+        );
+        assert!(matches!(
+            sql.bindings.iter().map(|b| b.as_ref()).collect::<Vec<_>>()[..],
+            [Value::Integer(1), Value::TypedNull(ValueType::Integer)]
+        ));
+
+        let mut qb = QueryBuilder::insert();
+        let sql = qb.into_("my_tbl").values([row(), row()]).sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"insert into "my_tbl" ("a", "b", "c") values ($1, null, $2), ($3, null, $4)"#
+        );
+        assert_eq!(sql.bindings.len(), 4);
+    }
+
+    #[test]
+    fn update_patch() {
+        #[derive(unnamed_qb_macro::Patch)]
+        #[qb(table = "users")]
+        struct UserPatch {
+            name: Option<String>,
+            email: Option<String>,
+            #[qb(rename = "bio")]
+            about: Option<Option<String>>,
+            updated_by: i64,
+        }
+
+        let patch = UserPatch {
+            name: Some("name".to_owned()),
+            email: None,
+            about: Some(None),
+            updated_by: 1,
+        };
+
+        let mut qb = QueryBuilder::patch(patch).unwrap();
+        let sql = qb.and_where(("id", "=", 1.value())).sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"update "users" set "name" = $1, "bio" = $2, "updated_by" = $3 where "id" = $4"#
+        );
+        assert!(matches!(
+            sql.bindings.iter().map(|b| b.as_ref()).collect::<Vec<_>>()[..],
+            [
+                Value::String(_),
+                Value::TypedNull(ValueType::String),
+                Value::BigInt(1),
+                Value::Integer(1)
+            ]
+        ));
+
+        let qb = QueryBuilder::patch(UserPatch {
+            name: None,
+            email: None,
+            about: None,
+            updated_by: 1,
+        })
+        .unwrap();
+
+        assert_eq!(qb.columns, ["updated_by"]);
+    }
+
+    #[test]
+    fn update_empty_patch() {
+        #[derive(unnamed_qb_macro::Patch)]
+        #[qb(table = "users")]
+        struct UserPatch {
+            name: Option<String>,
+            bio: Option<Option<String>>,
+        }
+
+        let patch = UserPatch {
+            name: None,
+            bio: None,
+        };
+
+        assert!(QueryBuilder::patch(patch).is_none());
+    }
+
     #[test]
     fn insert() {
         #[derive(unnamed_qb_macro::Row)]
@@ -696,6 +900,144 @@ mod test {
         assert_eq!(sql.bindings.len(), 20);
     }
 
+    #[test]
+    fn insert_row_attributes() {
+        #[derive(unnamed_qb_macro::Row)]
+        #[qb(table = "blog_posts", rename_all = "camelCase")]
+        struct Post {
+            #[qb(skip_insert)]
+            id: i64,
+            author_id: i32,
+            #[qb(rename = "body")]
+            text: String,
+            #[qb(skip)]
+            #[allow(dead_code)]
+            comments: Vec<String>,
+        }
+
+        let mut qb = QueryBuilder::insert();
+        let sql = qb
+            .value(Post {
+                id: 1,
+                author_id: 2,
+                text: "text".to_owned(),
+                comments: vec![],
+            })
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"insert into "blog_posts" ("authorId", "body") values ($1, $2)"#
+        );
+        assert_eq!(sql.bindings.len(), 2);
+    }
+
+    #[test]
+    fn insert_row_rename_all_uppercase() {
+        #[derive(unnamed_qb_macro::Row)]
+        #[qb(table = "posts", rename_all = "UPPERCASE")]
+        struct Post {
+            author_id: i32,
+        }
+
+        let mut qb = QueryBuilder::insert();
+        let sql = qb.value(Post { author_id: 1 }).sql::<TestDialect>();
+
+        assert_eq!(sql.sql, r#"insert into "posts" ("AUTHOR_ID") values ($1)"#);
+    }
+
+    #[test]
+    fn insert_flatten_generic_row() {
+        #[derive(unnamed_qb_macro::Row)]
+        struct Audit<'a> {
+            created_by: &'a str,
+            updated_by: Cow<'a, str>,
+        }
+
+        #[derive(unnamed_qb_macro::Row)]
+        #[qb(table = "posts")]
+        struct Post<'a, T> {
+            title: &'a str,
+            payload: T,
+            #[qb(flatten)]
+            audit: Audit<'a>,
+        }
+
+        #[derive(unnamed_qb_macro::Row)]
+        struct Tag(#[qb(rename = "tag")] String);
+
+        let author = "author".to_owned();
+        let mut qb = QueryBuilder::insert();
+        let sql = qb
+            .value(Post {
+                title: "title",
+                payload: 10,
+                audit: Audit {
+                    created_by: &author,
+                    updated_by: Cow::Borrowed(&author),
+                },
+            })
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"insert into "posts" ("title", "payload", "created_by", "updated_by") values ($1, $2, $3, $4)"#
+        );
+        assert_eq!(sql.bindings.len(), 4);
+
+        let mut qb = QueryBuilder::insert();
+        let sql = qb
+            .into_("tags")
+            .values([Tag("a".to_owned()), Tag("b".to_owned())])
+            .sql::<TestDialect>();
+
+        assert_eq!(sql.sql, r#"insert into "tags" ("tag") values ($1), ($2)"#);
+    }
+
+    #[test]
+    fn row_columns_flatten_type_param() {
+        use crate::Row;
+
+        #[derive(unnamed_qb_macro::Row)]
+        struct Named {
+            name: String,
+        }
+
+        #[derive(unnamed_qb_macro::Row)]
+        struct Aged {
+            #[qb(skip_insert)]
+            age: i32,
+        }
+
+        #[derive(unnamed_qb_macro::Row)]
+        struct WithId<R> {
+            id: i64,
+            #[qb(flatten)]
+            row: R,
+        }
+
+        assert_eq!(WithId::<Named>::columns(), ["id", "name"]);
+        assert_eq!(WithId::<Aged>::columns(), ["id", "age"]);
+        assert_eq!(WithId::<Aged>::insert_columns(), ["id"]);
+        assert!(std::ptr::eq(
+            WithId::<Named>::columns(),
+            WithId::<Named>::columns()
+        ));
+
+        #[derive(unnamed_qb_macro::Row)]
+        struct Tagged<'a, R> {
+            tag: &'a str,
+            #[qb(flatten)]
+            row: R,
+        }
+
+        assert_eq!(
+            Tagged::<WithId<Named>>::columns(),
+            ["tag", "id", "name"]
+        );
+        assert_eq!(Tagged::<WithId<Aged>>::insert_columns(), ["tag", "id"]);
+    }
+
     #[test]
     fn insert_from_sub_query() {
         let columns = &["a", "b", "c"];
@@ -766,4 +1108,672 @@ mod test {
 
         assert_eq!(sql.sql, r#"select * from "table" group by "column""#);
     }
+
+    #[test]
+    fn relation_quote_escaping() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .columns(vec![r#"tbl.a"; drop table x; --"#])
+            .from("tbl")
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select "tbl"."a""; drop table x; --" from "tbl""#
+        );
+    }
+
+    #[test]
+    fn identifier_with_dots() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .push_column(Identifier::new("my.column"))
+            .from("tbl")
+            .and_where((Identifier::new(r#"we"ird"#), 1.value()))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select "my.column" from "tbl" where "we""ird" = $1"#
+        );
+    }
+
+    #[test]
+    fn order_by_allow_list() {
+        let sortable = AllowList::new().allow("created", "tbl.created_at");
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("tbl")
+            .order_by((sortable.column("created").unwrap(), SqlKeyword::Desc))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "tbl" order by "tbl"."created_at" desc"#
+        );
+        assert!(sortable.column("password").is_err());
+    }
+
+    #[test]
+    fn raw_escaped_and_named() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from(
+                "tbl where data ?? 'key' and created_at > :since and id = ?"
+                    .raw()
+                    .bindings(vec![1.value()])
+                    .bind("since", "2020-01-01".value()),
+            )
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from tbl where data ? 'key' and created_at > $1 and id = $2"#
+        );
+        assert_eq!(sql.bindings.len(), 2);
+    }
+
+    #[test]
+    fn raw_bindings_mismatch() {
+        let mut qb = QueryBuilder::select();
+        qb.from("?".raw().bindings(vec![1.value(), 2.value()]));
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::RawBindingsMismatch {
+                placeholders: 1,
+                bindings: 2
+            }
+        ));
+
+        let mut qb = QueryBuilder::select();
+        qb.from("users")
+            .and_where(("a", "=", "? + ?".raw().bindings([1.value()])));
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::RawBindingsMismatch {
+                placeholders: 2,
+                bindings: 1
+            }
+        ));
+
+        let mut qb = QueryBuilder::select();
+        qb.push_column("count(?)".raw()).from("users");
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::RawBindingsMismatch {
+                placeholders: 1,
+                bindings: 0
+            }
+        ));
+
+        let mut qb = QueryBuilder::delete();
+        qb.from("users").and_where(("b", "=", ":b".raw()));
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::MissingNamedBinding(name) if name == "b"
+        ));
+    }
+
+    #[test]
+    fn invalid_arg_is_reported_when_rendered() {
+        struct Unknown;
+
+        impl<'a> TryIntoArg<'a> for Unknown {
+            type E = Error;
+
+            fn try_into_arg(self) -> Result<Arg<'a>, Error> {
+                Err(ErrorKind::UnknownField("unknown".to_owned()).into())
+            }
+        }
+
+        let mut qb = QueryBuilder::select();
+        qb.from("users").and_where((Unknown, "=", 1.value()));
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::InvalidArg(reason) if reason == "unknown field `unknown`"
+        ));
+
+        let mut qb = QueryBuilder::select();
+        qb.from("users")
+            .and_where_any(Unknown, vec![1, 2])
+            .or_where_contains("tags", Unknown);
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::InvalidArg(_)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "raw sql has 1 placeholders but 0 bindings")]
+    fn raw_bindings_mismatch_sql_panics() {
+        let mut qb = QueryBuilder::select();
+        qb.from("?".raw());
+
+        qb.sql::<TestDialect>();
+    }
+
+    #[test]
+    fn raw_fragments() {
+        let mut sub_query = QueryBuilder::select();
+        sub_query
+            .columns(vec!["id"])
+            .from("orders")
+            .and_where(("total", ">", 100.value()));
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("users")
+            .and_where(("age", ">", 18.value()))
+            .and_where((
+                "{col} = ? and {id} in {sub} and {expr}"
+                    .raw()
+                    .bindings(vec!["x".value()])
+                    .ident("col", "users.name")
+                    .ident("id", "users.order_id")
+                    .fragment("sub", sub_query)
+                    .fragment(
+                        "expr",
+                        "lower(?) = :v"
+                            .raw()
+                            .bindings(vec!["A".value()])
+                            .bind("v", "a".value()),
+                    ),
+                "=",
+                "true".raw(),
+            ))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "users" where "age" > $1 and "users"."name" = $2 and "users"."order_id" in (select "id" from "orders" where "total" > $3) and lower($4) = $5 = true"#
+        );
+        assert_eq!(sql.bindings.len(), 5);
+    }
+
+    #[test]
+    fn raw_fragment_bindings_mismatch() {
+        let mut qb = QueryBuilder::select();
+        qb.from("users").and_where((
+            "{expr}"
+                .raw()
+                .fragment("expr", "lower(?) = ?".raw().bindings(vec!["A".value()])),
+            "=",
+            "true".raw(),
+        ));
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::RawBindingsMismatch {
+                placeholders: 2,
+                bindings: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn where_in_values() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("tbl")
+            .and_where(("id", "in", vec![1.value(), 2.value(), 3.value()]))
+            .and_where(("name", "not in", vec!["a".value()]))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "tbl" where "id" in ($1, $2, $3) and "name" not in ($4)"#
+        );
+    }
+
+    #[test]
+    fn array_conditions() {
+        let ids: Vec<i32> = (0..1000).collect();
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("tbl")
+            .and_where_any("id", ids)
+            .and_where_contains("tags", vec!["a", "b"].value())
+            .or_where_contained_by("tags", vec!["c"].value())
+            .and_where_overlaps("tags", "other_tags")
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "tbl" where "id" = any($1) and "tags" @> $2 or "tags" <@ $3 and "tags" && "other_tags""#
+        );
+        assert_eq!(sql.bindings.len(), 3);
+    }
+
+    #[test]
+    fn custom_value_cast() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Ltree(&'static str);
+
+        impl CustomValue for Ltree {
+            fn cast_type(&self) -> Option<&str> {
+                Some("ltree")
+            }
+
+            #[cfg(feature = "sqlx")]
+            fn encode_postgres(
+                &self,
+                arg: crate::sql_dialect::postgres::PgArgument<'_>,
+            ) -> Result<crate::sql_dialect::postgres::PgEncoded, crate::error::Error> {
+                Ok(arg.bind(self.0))
+            }
+
+            #[cfg(feature = "tokio-postgres")]
+            fn to_tokio_postgres(&self) -> Box<dyn tokio_postgres::types::ToSql + Sync> {
+                Box::new(self.0)
+            }
+
+            #[cfg(feature = "rusqlite")]
+            fn to_rusqlite(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+                rusqlite::ToSql::to_sql(self.0)
+            }
+        }
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("tbl")
+            .and_where(("path", "<@", Value::custom(Ltree("a.b"))))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "tbl" where "path" <@ cast($1 as ltree)"#
+        );
+    }
+
+    #[test]
+    fn typed_null_bindings() {
+        #[derive(unnamed_qb_macro::Row)]
+        struct TestRow {
+            a: Option<String>,
+            b: Option<i64>,
+        }
+
+        let mut qb = QueryBuilder::insert();
+        let sql = qb
+            .into_("tbl")
+            .value(TestRow { a: None, b: None })
+            .sql::<TestDialect>();
+
+        assert_eq!(sql.sql, r#"insert into "tbl" ("a", "b") values ($1, $2)"#);
+        assert!(matches!(
+            sql.bindings.iter().map(|b| b.as_ref()).collect::<Vec<_>>()[..],
+            [
+                Value::TypedNull(ValueType::String),
+                Value::TypedNull(ValueType::BigInt)
+            ]
+        ));
+    }
+
+    #[test]
+    fn cast_expression() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .push_column(crate::cast("id", "text"))
+            .from("tbl")
+            .and_where(("data", "=", "{}".value().cast_as("jsonb")))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select cast("id" as text) from "tbl" where "data" = cast($1 as jsonb)"#
+        );
+    }
+
+    #[test]
+    fn cast_raw_bindings_mismatch() {
+        let mut qb = QueryBuilder::select();
+        qb.push_column(crate::cast("? + ?".raw().bindings(vec![1.value()]), "int"))
+            .from("tbl");
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::RawBindingsMismatch {
+                placeholders: 2,
+                bindings: 1
+            }
+        ));
+    }
+
+    fn owned_query(table: String, column: String, value: String) -> SelectQuery<'static> {
+        let mut qb = QueryBuilder::select();
+
+        qb.push_column(column.clone())
+            .from(table)
+            .and_where((column, "=", value.value()));
+
+        qb.into_owned()
+    }
+
+    #[test]
+    fn owned_select() {
+        let qb = owned_query("tbl".to_owned(), "name".to_owned(), "x".to_owned());
+        let sql = qb.sql::<TestDialect>().into_owned();
+
+        assert_eq!(sql.sql, r#"select "name" from "tbl" where "name" = $1"#);
+        assert!(matches!(
+            sql.bindings.iter().map(|b| b.as_ref()).collect::<Vec<_>>()[..],
+            [Value::String(Cow::Owned(_))]
+        ));
+    }
+
+    #[test]
+    fn render_then_mutate() {
+        let mut qb = QueryBuilder::select();
+        qb.from("tbl").and_where(("a", "=", 1.value()));
+
+        let first = qb.to_sql::<TestDialect>();
+        qb.and_where(("b", "=", 2.value()));
+        let second = qb.to_sql::<TestDialect>();
+
+        assert_eq!(first.sql, r#"select * from "tbl" where "a" = $1"#);
+        assert_eq!(first.bindings.len(), 1);
+        assert_eq!(
+            second.sql,
+            r#"select * from "tbl" where "a" = $1 and "b" = $2"#
+        );
+        assert_eq!(second.bindings.len(), 2);
+    }
+
+    #[test]
+    fn count_query() {
+        let mut qb = QueryBuilder::select();
+        qb.columns(["id", "name"])
+            .from("users")
+            .and_where(("active", "=", true.value()))
+            .order_by(("id", SqlKeyword::Desc))
+            .limit(10)
+            .offset(20);
+
+        let count = qb.to_count_query();
+        let sql = count.sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select count(*) from "users" where "active" = $1"#
+        );
+        assert_eq!(sql.bindings.len(), 1);
+    }
+
+    #[test]
+    fn count_query_grouped() {
+        let mut qb = QueryBuilder::select();
+        qb.columns(["author_id"])
+            .from("posts")
+            .and_where(("published", "=", true.value()))
+            .group_by("author_id")
+            .limit(10);
+
+        let count = qb.to_count_query();
+        let sql = count.sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select count(*) from (select "author_id" from "posts" where "published" = $1 group by "author_id") as "t""#
+        );
+    }
+
+    #[test]
+    fn keyset_row_values() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("posts")
+            .and_where(("published", "=", true.value()))
+            .order_by(("created_at", SqlKeyword::Asc))
+            .order_by(("id", SqlKeyword::Asc))
+            .after(crate::Cursor::new([1.value(), 2.value()]))
+            .unwrap()
+            .limit(10)
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "posts" where "published" = $1 and ("created_at", "id") > ($2, $3) order by "created_at" asc ,"id" asc limit 10"#
+        );
+        assert_eq!(sql.bindings.len(), 3);
+    }
+
+    #[test]
+    fn keyset_mixed_directions() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("posts")
+            .and_where(("published", "=", true.value()))
+            .order_by(("rating", SqlKeyword::Desc))
+            .order_by(("id", SqlKeyword::Asc))
+            .after(crate::Cursor::new([5.value(), 2.value()]))
+            .unwrap()
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "posts" where "published" = $1 and("rating" < $2 or("rating" = $3 and "id" > $4)) order by "rating" desc ,"id" asc"#
+        );
+        assert_eq!(sql.bindings.len(), 4);
+    }
+
+    #[test]
+    fn keyset_expanded() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("posts")
+            .order_by(("id", SqlKeyword::Desc))
+            .after_expanded(crate::Cursor::new([2.value()]))
+            .unwrap()
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "posts" where "id" < $1 order by "id" desc"#
+        );
+    }
+
+    #[test]
+    fn keyset_cursor_mismatch() {
+        let mut qb = QueryBuilder::select();
+        qb.from("posts").order_by(("id", SqlKeyword::Desc));
+
+        assert!(qb.after(crate::Cursor::new([])).is_err());
+    }
+
+    #[test]
+    fn keyset_after_or_where() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("posts")
+            .and_where(("published", "=", true.value()))
+            .or_where(("pinned", "=", true.value()))
+            .order_by(("id", SqlKeyword::Asc))
+            .after(crate::Cursor::new([2.value()]))
+            .unwrap()
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "posts" where ("published" = $1 or "pinned" = $2) and ("id") > ($3) order by "id" asc"#
+        );
+    }
+
+    #[test]
+    fn keyset_null_cursor() {
+        let mut qb = QueryBuilder::select();
+        qb.from("posts").order_by(("id", SqlKeyword::Asc));
+
+        assert!(matches!(
+            qb.after(crate::Cursor::new([Value::Null])).unwrap_err().kind(),
+            crate::error::ErrorKind::InvalidCursor(_)
+        ));
+        assert!(qb.after(crate::Cursor::new([None::<i32>.value()])).is_err());
+
+        let mut qb = QueryBuilder::select();
+        qb.from("posts")
+            .order_by(("rating", SqlKeyword::Desc, SqlKeyword::NullsLast));
+
+        assert!(qb.after(crate::Cursor::new([1.value()])).is_err());
+    }
+
+    #[test]
+    fn table_columns() {
+        use crate::Table;
+
+        #[derive(unnamed_qb_macro::Table)]
+        #[qb(table = "users")]
+        #[allow(dead_code)]
+        struct User {
+            id: i64,
+            #[qb(rename = "mail")]
+            email: String,
+        }
+
+        #[derive(unnamed_qb_macro::Table)]
+        #[qb(table = "posts")]
+        #[allow(dead_code)]
+        struct Post {
+            id: i64,
+            author_id: i64,
+        }
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .columns([Post::ID, Post::AUTHOR_ID])
+            .push_column(User::EMAIL)
+            .from(Post::TABLE)
+            .join(User::TABLE, User::ID, "=", Post::AUTHOR_ID)
+            .and_where((User::EMAIL, "=", "a@b.c".value()))
+            .order_by((Post::ID, SqlKeyword::Desc))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select "posts"."id", "posts"."author_id", "users"."mail" from "posts" join "users" on "users"."id" = "posts"."author_id" where "users"."mail" = $1 order by "posts"."id" desc"#
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn typed_json_conditions() {
+        use crate::Json;
+        use std::collections::HashMap;
+
+        #[derive(unnamed_qb_macro::Table)]
+        #[qb(table = "docs")]
+        #[allow(dead_code)]
+        struct Doc {
+            data: Json<HashMap<String, i32>>,
+            pairs: Json<HashMap<(i32, i32), i32>>,
+        }
+
+        let mut qb = QueryBuilder::select();
+        qb.from("docs")
+            .and_where(Doc::DATA.eq(Json(HashMap::from([("a".to_owned(), 1)]))));
+
+        assert_eq!(
+            qb.sql::<TestDialect>().sql,
+            r#"select * from "docs" where "docs"."data" = $1"#
+        );
+
+        let mut qb = QueryBuilder::select();
+        qb.from("docs")
+            .and_where(Doc::PAIRS.eq(Json(HashMap::from([((1, 2), 1)]))));
+
+        assert!(matches!(
+            qb.try_sql::<TestDialect>().err().unwrap().kind(),
+            ErrorKind::InvalidArg(_)
+        ));
+    }
+
+    #[test]
+    fn typed_conditions() {
+        #[derive(unnamed_qb_macro::Table)]
+        #[qb(table = "users")]
+        #[allow(dead_code)]
+        struct User {
+            id: i64,
+            age: i32,
+            email: String,
+            deleted_at: Option<i64>,
+        }
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("users")
+            .and_where(User::AGE.gte(18))
+            .and_where(User::EMAIL.like("%@example.com"))
+            .and_where(User::ID.in_([1, 2]))
+            .or_where(User::DELETED_AT.is_null())
+            .and_where(User::ID.ne(User::ID))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "users" where "users"."age" >= $1 and "users"."email" like $2 and "users"."id" in ($3, $4) or "users"."deleted_at" is null and "users"."id" <> "users"."id""#
+        );
+        assert!(matches!(
+            sql.bindings.iter().map(|b| b.as_ref()).collect::<Vec<_>>()[..],
+            [
+                Value::Integer(18),
+                Value::String(_),
+                Value::BigInt(1),
+                Value::BigInt(2)
+            ]
+        ));
+    }
+
+    #[test]
+    fn typed_conditions_in_and_arrays() {
+        #[derive(unnamed_qb_macro::Table)]
+        #[qb(table = "posts")]
+        #[allow(dead_code)]
+        struct Post {
+            id: i64,
+            tags: Vec<String>,
+            body: Vec<u8>,
+        }
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .from("posts")
+            .and_where(Post::ID.in_(Vec::<i64>::new()))
+            .and_where(Post::TAGS.eq(vec!["a", "b"]))
+            .and_where(Post::BODY.ne([1u8, 2].as_slice()))
+            .sql::<TestDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select * from "posts" where 1 = 0 and "posts"."tags" = $1 and "posts"."body" <> $2"#
+        );
+        assert_eq!(sql.bindings.len(), 2);
+    }
+
+    #[test]
+    fn select_row() {
+        #[derive(unnamed_qb_macro::Row)]
+        #[cfg_attr(feature = "sqlx", derive(unnamed_qb_macro::FromRow))]
+        #[allow(dead_code)]
+        struct User {
+            id: i64,
+            #[qb(rename = "mail")]
+            email: String,
+            #[qb(skip)]
+            cache: Vec<u8>,
+        }
+
+        let mut qb = QueryBuilder::select();
+        let sql = qb.select_row::<User>().from("users").sql::<TestDialect>();
+
+        assert_eq!(sql.sql, r#"select "id", "mail" from "users""#);
+    }
 }
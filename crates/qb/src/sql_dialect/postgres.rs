@@ -1,17 +1,100 @@
 use super::SqlDialect;
-use crate::query_builder::*;
-use sqlx::Arguments;
+#[cfg(feature = "sqlx")]
+use super::SqlxDialect;
+use crate::{error::Error, query_builder::*};
+#[cfg(feature = "sqlx")]
+use sqlx::{postgres::PgArguments, Arguments};
+use std::borrow::Cow;
+
+#[cfg(feature = "sqlx")]
+fn add_null(args: &mut PgArguments, value_type: ValueType) {
+    match value_type {
+        ValueType::String => args.add::<Option<&str>>(None),
+        ValueType::SmallInt => args.add::<Option<i16>>(None),
+        ValueType::Integer => args.add::<Option<i32>>(None),
+        ValueType::BigInt => args.add::<Option<i64>>(None),
+        ValueType::Real => args.add::<Option<f32>>(None),
+        ValueType::Double => args.add::<Option<f64>>(None),
+        #[cfg(feature = "rust_decimal")]
+        ValueType::Decimal => args.add::<Option<rust_decimal::Decimal>>(None),
+        #[cfg(feature = "bigdecimal")]
+        ValueType::BigDecimal => args.add::<Option<bigdecimal::BigDecimal>>(None),
+        ValueType::Boolean => args.add::<Option<bool>>(None),
+        #[cfg(feature = "chrono")]
+        ValueType::ChronoDate => args.add::<Option<chrono::NaiveDate>>(None),
+        #[cfg(feature = "chrono")]
+        ValueType::ChronoTime => args.add::<Option<chrono::NaiveTime>>(None),
+        #[cfg(feature = "chrono")]
+        ValueType::ChronoDateTime => args.add::<Option<chrono::NaiveDateTime>>(None),
+        #[cfg(feature = "chrono")]
+        ValueType::ChronoDateTimeUtc => args.add::<Option<chrono::DateTime<chrono::Utc>>>(None),
+        #[cfg(feature = "chrono")]
+        ValueType::ChronoDuration => args.add::<Option<chrono::Duration>>(None),
+        #[cfg(feature = "time")]
+        ValueType::TimeDate => args.add::<Option<time::Date>>(None),
+        #[cfg(feature = "time")]
+        ValueType::TimeTime => args.add::<Option<time::Time>>(None),
+        #[cfg(feature = "time")]
+        ValueType::TimeDateTime => args.add::<Option<time::PrimitiveDateTime>>(None),
+        #[cfg(feature = "time")]
+        ValueType::TimeDateTimeTz => args.add::<Option<time::OffsetDateTime>>(None),
+        #[cfg(feature = "time")]
+        ValueType::TimeDuration => args.add::<Option<time::Duration>>(None),
+        #[cfg(feature = "uuid")]
+        ValueType::Uuid => args.add::<Option<uuid::Uuid>>(None),
+        #[cfg(feature = "json")]
+        ValueType::Json => args.add::<Option<serde_json::Value>>(None),
+        ValueType::Bytes => args.add::<Option<&[u8]>>(None),
+        ValueType::Array(array_type) => match array_type {
+            ArrayType::SmallInt => args.add::<Option<Vec<i16>>>(None),
+            ArrayType::Integer => args.add::<Option<Vec<i32>>>(None),
+            ArrayType::BigInt => args.add::<Option<Vec<i64>>>(None),
+            ArrayType::Real => args.add::<Option<Vec<f32>>>(None),
+            ArrayType::Double => args.add::<Option<Vec<f64>>>(None),
+            ArrayType::Boolean => args.add::<Option<Vec<bool>>>(None),
+            ArrayType::String => args.add::<Option<Vec<String>>>(None),
+            #[cfg(feature = "uuid")]
+            ArrayType::Uuid => args.add::<Option<Vec<uuid::Uuid>>>(None),
+        },
+    }
+}
+
+/// Slot for the single parameter a [`CustomValue`] binds.
+#[cfg(feature = "sqlx")]
+pub struct PgArgument<'a> {
+    args: &'a mut PgArguments,
+}
+
+/// Returned by [`PgArgument::bind`], proves that the parameter was bound.
+#[cfg(feature = "sqlx")]
+pub struct PgEncoded(());
+
+#[cfg(feature = "sqlx")]
+impl<'a> PgArgument<'a> {
+    pub fn bind<'q, T>(self, value: T) -> PgEncoded
+    where
+        T: 'q + Send + sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+    {
+        self.args.add(value);
+        PgEncoded(())
+    }
+}
+
+/// Marker of sql written for Postgres.
+#[derive(Debug)]
+pub struct Postgres;
 
 #[derive(Debug, Default)]
 pub struct PostgresSqlDialect<'a> {
     pub sql: String,
     pub bindings: Vec<&'a Value<'a>>,
+    pub error: Option<Error>,
 }
 
 impl<'a> SqlDialect<'a> for PostgresSqlDialect<'a> {
     const RELATION_QUOTE: char = '"';
 
-    type SqlxQb = sqlx::QueryBuilder<'a, sqlx::postgres::Postgres>;
+    type Database = Postgres;
 
     fn init() -> Self {
         Self::default()
@@ -21,14 +104,31 @@ impl<'a> SqlDialect<'a> for PostgresSqlDialect<'a> {
         super::Dialect::Postgres
     }
 
-    fn sql(self) -> super::Sql<'a> {
+    fn sql(self) -> super::Sql<'a, Postgres> {
         super::Sql {
             sql: self.sql,
-            bindings: self.bindings,
+            bindings: self.bindings.into_iter().map(Cow::Borrowed).collect(),
             dialect: Self::dialect(),
+            database: Default::default(),
         }
     }
 
+    fn write_cast<F>(&mut self, type_name: &str, simple: bool, write_expr: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        if simple {
+            write_expr(self);
+        } else {
+            self.write_char('(');
+            write_expr(self);
+            self.write_char(')');
+        }
+
+        self.write_str("::");
+        self.write_str(type_name);
+    }
+
     fn write_char(&mut self, ch: char) {
         self.sql.push(ch);
     }
@@ -50,18 +150,140 @@ impl<'a> SqlDialect<'a> for PostgresSqlDialect<'a> {
         self.bindings.len()
     }
 
-    fn into_sqlx_qb(self) -> Self::SqlxQb {
-        let mut args = sqlx::postgres::PgArguments::default();
+    fn set_error(&mut self, error: Error) {
+        self.error.get_or_insert(error);
+    }
 
-        self.bindings.into_iter().for_each(|binding| match binding {
+    fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'a> SqlxDialect<'a> for PostgresSqlDialect<'a> {
+    type SqlxQb = sqlx::QueryBuilder<'a, sqlx::postgres::Postgres>;
+
+    fn into_sqlx_qb(mut self) -> Result<Self::SqlxQb, Error> {
+        if let Some(error) = self.take_error() {
+            return Err(error);
+        }
+
+        Ok(sqlx::QueryBuilder::with_arguments(
+            self.sql,
+            encode_bindings(self.bindings)?,
+        ))
+    }
+}
+
+#[cfg(feature = "sqlx")]
+pub(crate) fn encode_bindings<'b, 'v: 'b>(
+    bindings: impl IntoIterator<Item = &'b Value<'v>>,
+) -> Result<PgArguments, Error> {
+    let mut args = PgArguments::default();
+
+    for binding in bindings {
+        match binding {
+            Value::SmallInt(v) => args.add(v),
             Value::Integer(v) => args.add(v),
             Value::BigInt(v) => args.add(v),
-            // todo: check it
+            Value::Real(v) => args.add(v),
+            Value::Double(v) => args.add(v),
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(v) => args.add(v),
+            #[cfg(feature = "bigdecimal")]
+            Value::BigDecimal(v) => args.add(v),
+            Value::Boolean(v) => args.add(v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDate(v) => args.add(v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoTime(v) => args.add(v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTime(v) => args.add(v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeUtc(v) => args.add(v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDuration(v) => args.add(v),
+            #[cfg(feature = "time")]
+            Value::TimeDate(v) => args.add(v),
+            #[cfg(feature = "time")]
+            Value::TimeTime(v) => args.add(v),
+            #[cfg(feature = "time")]
+            Value::TimeDateTime(v) => args.add(v),
+            #[cfg(feature = "time")]
+            Value::TimeDateTimeTz(v) => args.add(v),
+            #[cfg(feature = "time")]
+            Value::TimeDuration(v) => args.add(v),
+            #[cfg(feature = "uuid")]
+            Value::Uuid(v) => args.add(v),
+            #[cfg(feature = "json")]
+            Value::Json(v) => args.add(v),
+            Value::Bytes(v) => args.add(v.as_ref()),
+            Value::Array(array) => match array {
+                Array::SmallInt(v) => args.add(v.as_ref()),
+                Array::Integer(v) => args.add(v.as_ref()),
+                Array::BigInt(v) => args.add(v.as_ref()),
+                Array::Real(v) => args.add(v.as_ref()),
+                Array::Double(v) => args.add(v.as_ref()),
+                Array::Boolean(v) => args.add(v.as_ref()),
+                Array::String(v) => args.add(v.as_slice()),
+                #[cfg(feature = "uuid")]
+                Array::Uuid(v) => args.add(v.as_ref()),
+            },
+            Value::Custom(v) => {
+                v.encode_postgres(PgArgument { args: &mut args })?;
+            }
+            // never bound, the dialect writes an untyped null as a literal
             Value::Null => args.add::<Option<i32>>(None),
+            Value::TypedNull(value_type) => add_null(&mut args, *value_type),
             Value::String(s) => args.add(s),
-            _ => panic!("unsuppoted"),
-        });
+        }
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod test {
+    use super::PostgresSqlDialect;
+    use crate::{cast, prelude::*, RawExt};
+
+    #[test]
+    fn cast_expression() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .push_column(cast("id", "text"))
+            .from("tbl")
+            .and_where(("ids", "=", vec![1, 2].value().cast_as("int8[]")))
+            .and_where((
+                "a",
+                "=",
+                cast("? + 1".raw().bindings(vec![1.value()]), "int"),
+            ))
+            .sql::<PostgresSqlDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select "id"::text from "tbl" where "ids" = $1::int8[] and "a" = ($2 + 1)::int"#
+        );
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn custom_value_without_encoding() {
+        #[derive(Debug)]
+        struct Point;
+
+        impl crate::CustomValue for Point {}
+
+        let mut qb = QueryBuilder::select();
+        qb.from("tbl")
+            .and_where(("p", "=", crate::Value::custom(Point)));
+
+        let err = qb.sqlx_qb::<PostgresSqlDialect>().err().unwrap();
 
-        sqlx::QueryBuilder::with_arguments(self.sql, args)
+        assert_eq!(
+            err.to_string(),
+            "unsupported value: Point can't be encoded by sqlx"
+        );
     }
 }
@@ -0,0 +1,96 @@
+use super::SqlDialect;
+use crate::{error::Error, query_builder::*};
+use std::borrow::Cow;
+
+/// Marker of sql written for SQLite.
+#[derive(Debug)]
+pub struct Sqlite;
+
+#[derive(Debug, Default)]
+pub struct SqliteSqlDialect<'a> {
+    pub sql: String,
+    pub bindings: Vec<&'a Value<'a>>,
+    pub error: Option<Error>,
+}
+
+impl<'a> SqlDialect<'a> for SqliteSqlDialect<'a> {
+    const RELATION_QUOTE: char = '"';
+
+    type Database = Sqlite;
+
+    fn init() -> Self {
+        Self::default()
+    }
+
+    fn dialect() -> super::Dialect {
+        super::Dialect::Sqlite
+    }
+
+    fn sql(self) -> super::Sql<'a, Sqlite> {
+        super::Sql {
+            sql: self.sql,
+            bindings: self.bindings.into_iter().map(Cow::Borrowed).collect(),
+            dialect: Self::dialect(),
+            database: Default::default(),
+        }
+    }
+
+    fn write_char(&mut self, ch: char) {
+        self.sql.push(ch);
+    }
+
+    fn write_str<S: AsRef<str>>(&mut self, sql: S) {
+        self.sql.push_str(sql.as_ref());
+    }
+
+    fn push_binding(&mut self, value: &'a Value<'a>) -> usize {
+        self.bindings.push(value);
+        self.bindings.len()
+    }
+
+    fn extend_bindings(&mut self, bindings: impl IntoIterator<Item = &'a Value<'a>>) {
+        self.bindings.extend(bindings);
+    }
+
+    fn get_bindings_count(&self) -> usize {
+        self.bindings.len()
+    }
+
+    fn set_error(&mut self, error: Error) {
+        self.error.get_or_insert(error);
+    }
+
+    fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    fn write_placeholder(&mut self, value: &'a Value<'a>) {
+        let idx = self.push_binding(value);
+        self.write_char('?');
+        self.write_str(idx.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SqliteSqlDialect;
+    use crate::{cast, prelude::*, RawExt};
+
+    #[test]
+    fn numbered_placeholders() {
+        let mut qb = QueryBuilder::select();
+        let sql = qb
+            .push_column(cast("id", "text"))
+            .from("tbl")
+            .and_where(("a", "=", 1.value()))
+            .and_where(("b", "in", vec![2.value(), 3.value()]))
+            .and_where(("c", "=", "? + 1".raw().bindings(vec![4.value()])))
+            .sql::<SqliteSqlDialect>();
+
+        assert_eq!(
+            sql.sql,
+            r#"select cast("id" as text) from "tbl" where "a" = ?1 and "b" in (?2, ?3) and "c" = ?4 + 1"#
+        );
+        assert_eq!(sql.bindings.len(), 4);
+    }
+}
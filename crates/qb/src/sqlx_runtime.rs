@@ -0,0 +1,263 @@
+use crate::{
+    error::Error,
+    query_builder::{DeleteQuery, InsertQuery, SelectQuery, UpdateQuery},
+    sql_dialect::{
+        postgres::{self, encode_bindings, PostgresSqlDialect},
+        Sql,
+    },
+};
+use futures_channel::mpsc;
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, BoxStream, StreamExt},
+};
+use sqlx::{
+    postgres::{PgArguments, PgQueryResult, PgRow},
+    Executor, FromRow, Postgres,
+};
+use std::future::{poll_fn, Future};
+
+// sqlx 0.7 has no encode error, a raw fragment which doesn't match its
+// bindings or a binding without an encoding is a setup mistake, so it's
+// reported as a configuration one
+fn query_error(err: Error) -> sqlx::Error {
+    sqlx::Error::Configuration(Box::new(err))
+}
+
+impl<'a> Sql<'a, postgres::Postgres> {
+    fn arguments(&self) -> Result<PgArguments, sqlx::Error> {
+        encode_bindings(self.bindings.iter().map(AsRef::as_ref)).map_err(query_error)
+    }
+
+    pub async fn fetch_all<'c, O, E>(&self, executor: E) -> Result<Vec<O>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+        O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        log::debug!(target: "unnamed_qb", "{}", self.sql);
+
+        sqlx::query_as_with(&self.sql, self.arguments()?)
+            .fetch_all(executor)
+            .await
+    }
+
+    pub async fn fetch_one<'c, O, E>(&self, executor: E) -> Result<O, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+        O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        log::debug!(target: "unnamed_qb", "{}", self.sql);
+
+        sqlx::query_as_with(&self.sql, self.arguments()?)
+            .fetch_one(executor)
+            .await
+    }
+
+    pub async fn fetch_optional<'c, O, E>(&self, executor: E) -> Result<Option<O>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+        O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        log::debug!(target: "unnamed_qb", "{}", self.sql);
+
+        sqlx::query_as_with(&self.sql, self.arguments()?)
+            .fetch_optional(executor)
+            .await
+    }
+
+    /// Streams rows. The stream borrows the rendered sql, so render the query
+    /// first: `let sql = query.sql::<PostgresSqlDialect>(); sql.fetch(&pool)`,
+    /// or use `fetch` of the query which keeps the sql in the stream.
+    pub fn fetch<'q, 'c: 'q, O, E>(&'q self, executor: E) -> BoxStream<'q, Result<O, sqlx::Error>>
+    where
+        E: 'q + Executor<'c, Database = Postgres>,
+        O: 'q + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        log::debug!(target: "unnamed_qb", "{}", self.sql);
+
+        match self.arguments() {
+            Ok(arguments) => sqlx::query_as_with(&self.sql, arguments).fetch(executor),
+            Err(err) => stream::once(future::ready(Err(err))).boxed(),
+        }
+    }
+
+    pub async fn execute<'c, E>(&self, executor: E) -> Result<PgQueryResult, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        log::debug!(target: "unnamed_qb", "{}", self.sql);
+
+        sqlx::query_with(&self.sql, self.arguments()?)
+            .execute(executor)
+            .await
+    }
+}
+
+impl<'a> Sql<'a, postgres::Postgres> {
+    // the sqlx stream borrows the sql, so both are kept by the driver
+    fn into_stream<'q, 'c: 'q, O, E>(self, executor: E) -> BoxStream<'q, Result<O, sqlx::Error>>
+    where
+        'a: 'q,
+        E: 'q + Executor<'c, Database = Postgres>,
+        O: 'q + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        driven_stream(|mut sender| async move {
+            let mut rows = self.fetch(executor);
+
+            while let Some(row) = rows.next().await {
+                // the receiver is gone, nobody reads the rest
+                if poll_fn(|cx| sender.poll_ready(cx)).await.is_err()
+                    || sender.start_send(row).is_err()
+                {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+// items sent by the driver future, which yields nothing itself and is polled
+// along with the receiver
+fn driven_stream<'q, T, F>(drive: impl FnOnce(mpsc::Sender<T>) -> F) -> BoxStream<'q, T>
+where
+    T: 'q + Send,
+    F: 'q + Future<Output = ()> + Send,
+{
+    let (sender, receiver) = mpsc::channel(0);
+
+    stream::select(
+        receiver,
+        drive(sender)
+            .into_stream()
+            .filter_map(|()| future::ready(None)),
+    )
+    .boxed()
+}
+
+macro_rules! impl_execution {
+    ($($query:ident),*) => {
+        $(
+            impl<'a> $query<'a> {
+                pub async fn fetch_all<'c, O, E>(&self, executor: E) -> Result<Vec<O>, sqlx::Error>
+                where
+                    E: Executor<'c, Database = Postgres>,
+                    O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+                {
+                    self.try_sql::<PostgresSqlDialect>()
+                        .map_err(query_error)?
+                        .fetch_all(executor)
+                        .await
+                }
+
+                pub async fn fetch_one<'c, O, E>(&self, executor: E) -> Result<O, sqlx::Error>
+                where
+                    E: Executor<'c, Database = Postgres>,
+                    O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+                {
+                    self.try_sql::<PostgresSqlDialect>()
+                        .map_err(query_error)?
+                        .fetch_one(executor)
+                        .await
+                }
+
+                pub async fn fetch_optional<'c, O, E>(
+                    &self,
+                    executor: E,
+                ) -> Result<Option<O>, sqlx::Error>
+                where
+                    E: Executor<'c, Database = Postgres>,
+                    O: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+                {
+                    self.try_sql::<PostgresSqlDialect>()
+                        .map_err(query_error)?
+                        .fetch_optional(executor)
+                        .await
+                }
+
+                /// Streams rows, the stream keeps the rendered sql.
+                pub fn fetch<'q, 'c: 'q, O, E>(
+                    &'q self,
+                    executor: E,
+                ) -> BoxStream<'q, Result<O, sqlx::Error>>
+                where
+                    E: 'q + Executor<'c, Database = Postgres>,
+                    O: 'q + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+                {
+                    match self.try_sql::<PostgresSqlDialect>() {
+                        Ok(sql) => sql.into_stream(executor),
+                        Err(err) => stream::once(future::ready(Err(query_error(err)))).boxed(),
+                    }
+                }
+
+                pub async fn execute<'c, E>(&self, executor: E) -> Result<PgQueryResult, sqlx::Error>
+                where
+                    E: Executor<'c, Database = Postgres>,
+                {
+                    self.try_sql::<PostgresSqlDialect>()
+                        .map_err(query_error)?
+                        .execute(executor)
+                        .await
+                }
+            }
+        )*
+    };
+}
+
+impl_execution!(SelectQuery, InsertQuery, UpdateQuery, DeleteQuery);
+
+#[cfg(test)]
+mod test {
+    use crate::{prelude::*, sql_dialect::postgres::PostgresSqlDialect};
+    use sqlx::{PgConnection, PgPool};
+
+    fn assert_send<T: Send>(_: T) {}
+
+    // compile only, running needs a database and a runtime
+    #[allow(dead_code)]
+    async fn executors(pool: &PgPool, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+        let mut select = QueryBuilder::select();
+        select.from("users").and_where(("id", "=", 1.value()));
+
+        let _: Vec<(i32,)> = select.fetch_all(pool).await?;
+        let _: (i32,) = select.fetch_one(&mut *conn).await?;
+        let _: Option<(i32,)> = select.fetch_optional(pool).await?;
+
+        let mut tx = pool.begin().await?;
+        QueryBuilder::delete()
+            .from("users")
+            .and_where(("id", "=", 1.value()))
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        let sql = select.sql::<PostgresSqlDialect>();
+        let _rows = sql.fetch::<(i32,), _>(pool);
+        let _rows = select.fetch::<(i32,), _>(pool);
+        assert_send(
+            QueryBuilder::delete()
+                .from("users")
+                .fetch::<(i32,), _>(pool),
+        );
+
+        assert_send(select.fetch_all::<(i32,), _>(pool));
+
+        Ok(())
+    }
+
+    #[test]
+    fn driven_stream_yields_each_item() {
+        let stream = super::driven_stream(|mut sender| async move {
+            for item in 0..3 {
+                // waits for the reader, the channel has no buffer
+                std::future::poll_fn(|cx| sender.poll_ready(cx))
+                    .await
+                    .unwrap();
+                sender.start_send(item).unwrap();
+            }
+        });
+
+        let items = futures_executor::block_on_stream(stream).collect::<Vec<_>>();
+
+        assert_eq!(items, [0, 1, 2]);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    error::ErrorKind,
+    query_builder::{Array, Value},
+    sql_dialect::{postgres::Postgres, Sql},
+};
+use bytes::BytesMut;
+use std::error::Error;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+
+/// `NULL` which fits a parameter of any type.
+#[derive(Debug)]
+struct Null;
+
+impl ToSql for Null {
+    fn to_sql(&self, _: &Type, _: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        Ok(IsNull::Yes)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+/// Param which can't be encoded, fails when the statement is run instead of
+/// when it's built.
+#[derive(Debug)]
+struct Unsupported(String);
+
+impl ToSql for Unsupported {
+    fn to_sql(&self, _: &Type, _: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        Err(Box::new(crate::error::Error::from(
+            ErrorKind::UnsupportedValue(self.0.clone()),
+        )))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+pub(crate) fn unsupported(reason: String) -> Box<dyn ToSql + Sync> {
+    Box::new(Unsupported(reason))
+}
+
+impl<'a> Sql<'a, Postgres> {
+    /// Sql and params for `tokio_postgres::Client`, e.g.
+    /// `client.query_raw(&sql, params)`.
+    pub fn into_tokio_postgres(self) -> (String, Vec<Box<dyn ToSql + Sync>>) {
+        let params = self
+            .bindings
+            .into_iter()
+            .map(|binding| to_sql(binding.into_owned()))
+            .collect();
+
+        (self.sql, params)
+    }
+}
+
+fn to_sql(value: Value<'_>) -> Box<dyn ToSql + Sync> {
+    match value {
+        Value::String(v) => Box::new(v.into_owned()),
+        Value::SmallInt(v) => Box::new(v),
+        Value::Integer(v) => Box::new(v),
+        Value::BigInt(v) => Box::new(v),
+        Value::Real(v) => Box::new(v),
+        Value::Double(v) => Box::new(v),
+        #[cfg(feature = "rust_decimal")]
+        Value::Decimal(v) => Box::new(v),
+        #[cfg(feature = "bigdecimal")]
+        Value::BigDecimal(_) => unsupported("bigdecimal is not supported by tokio-postgres".into()),
+        Value::Boolean(v) => Box::new(v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDate(v) => Box::new(v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoTime(v) => Box::new(v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTime(v) => Box::new(v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeUtc(v) => Box::new(v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDuration(_) => {
+            unsupported("interval is not supported by tokio-postgres".into())
+        }
+        #[cfg(feature = "time")]
+        Value::TimeDate(v) => Box::new(v),
+        #[cfg(feature = "time")]
+        Value::TimeTime(v) => Box::new(v),
+        #[cfg(feature = "time")]
+        Value::TimeDateTime(v) => Box::new(v),
+        #[cfg(feature = "time")]
+        Value::TimeDateTimeTz(v) => Box::new(v),
+        #[cfg(feature = "time")]
+        Value::TimeDuration(_) => unsupported("interval is not supported by tokio-postgres".into()),
+        #[cfg(feature = "uuid")]
+        Value::Uuid(v) => Box::new(v),
+        #[cfg(feature = "json")]
+        Value::Json(v) => Box::new(v),
+        Value::Bytes(v) => Box::new(v.into_owned()),
+        Value::Array(array) => match array {
+            Array::SmallInt(v) => Box::new(v.into_owned()),
+            Array::Integer(v) => Box::new(v.into_owned()),
+            Array::BigInt(v) => Box::new(v.into_owned()),
+            Array::Real(v) => Box::new(v.into_owned()),
+            Array::Double(v) => Box::new(v.into_owned()),
+            Array::Boolean(v) => Box::new(v.into_owned()),
+            Array::String(v) => Box::new(v.into_iter().map(|s| s.into_owned()).collect::<Vec<_>>()),
+            #[cfg(feature = "uuid")]
+            Array::Uuid(v) => Box::new(v.into_owned()),
+        },
+        Value::Custom(v) => v.to_tokio_postgres(),
+        Value::Null | Value::TypedNull(_) => Box::new(Null),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::to_sql;
+    use crate::{prelude::*, sql_dialect::postgres::PostgresSqlDialect, CustomValue, Value};
+    use bytes::BytesMut;
+    use tokio_postgres::types::{IsNull, Type};
+
+    #[test]
+    fn params() {
+        let mut qb = QueryBuilder::select();
+        qb.from("users")
+            .and_where(("id", "=", 1.value()))
+            .and_where(("name", "=", "bob".value()));
+
+        let (sql, params) = qb.sql::<PostgresSqlDialect>().into_tokio_postgres();
+
+        assert_eq!(
+            sql,
+            r#"select * from "users" where "id" = $1 and "name" = $2"#
+        );
+        assert_eq!(params.len(), 2);
+
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            params[0].to_sql_checked(&Type::INT4, &mut buf),
+            Ok(IsNull::No)
+        ));
+        assert_eq!(&buf[..], &1i32.to_be_bytes());
+
+        assert!(params[1].to_sql_checked(&Type::INT4, &mut buf).is_err());
+    }
+
+    #[test]
+    fn null_fits_any_type() {
+        let mut buf = BytesMut::new();
+
+        for value in [Value::Null, None::<i32>.value()] {
+            assert!(matches!(
+                to_sql(value).to_sql_checked(&Type::TIMESTAMPTZ, &mut buf),
+                Ok(IsNull::Yes)
+            ));
+        }
+    }
+
+    #[test]
+    fn unsupported_fails_on_encode() {
+        #[derive(Debug)]
+        struct Point;
+
+        impl CustomValue for Point {}
+
+        let mut buf = BytesMut::new();
+        let err = to_sql(Value::custom(Point))
+            .to_sql_checked(&Type::POINT, &mut buf)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "unsupported value: Point can't be encoded by tokio-postgres"
+        );
+
+        #[cfg(feature = "time")]
+        assert!(to_sql(time::Duration::SECOND.value())
+            .to_sql_checked(&Type::INTERVAL, &mut buf)
+            .is_err());
+    }
+}
@@ -0,0 +1,8 @@
+#[test]
+fn derive_row() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    #[cfg(feature = "sqlx")]
+    t.pass("tests/ui/sqlx/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,14 @@
+use unnamed_qb::{Conditions, QueryBuilder, Table, ValueExt};
+
+#[derive(Table)]
+#[qb(table = "users")]
+struct User {
+    id: i64,
+    email: String,
+}
+
+fn main() {
+    let mut qb = QueryBuilder::select();
+    qb.from(User::TABLE)
+        .and_where((User::EMIAL, "=", "a@b.c".value()));
+}
@@ -0,0 +1,8 @@
+use unnamed_qb::Table;
+
+#[derive(Table)]
+struct User {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use unnamed_qb::Row;
+
+#[derive(Row)]
+struct MyRow(String, i32);
+
+fn main() {}
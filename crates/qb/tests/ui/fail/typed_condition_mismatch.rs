@@ -0,0 +1,12 @@
+use unnamed_qb::{Conditions, QueryBuilder, Table};
+
+#[derive(Table)]
+#[qb(table = "users")]
+struct User {
+    age: i32,
+}
+
+fn main() {
+    let mut qb = QueryBuilder::select();
+    qb.from(User::TABLE).and_where(User::AGE.gt("eighteen"));
+}
@@ -0,0 +1,13 @@
+use unnamed_qb::{Conditions, QueryBuilder, Table};
+
+#[derive(Table)]
+#[qb(table = "users")]
+struct User {
+    id: i64,
+    manager_id: i64,
+}
+
+fn main() {
+    let mut qb = QueryBuilder::select();
+    qb.from(User::TABLE).and_where(User::ID.in_([User::MANAGER_ID]));
+}
@@ -0,0 +1,9 @@
+use unnamed_qb::Row;
+
+#[derive(Row)]
+struct MyRow {
+    #[qb(column = "b")]
+    a: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use unnamed_qb::Row;
+
+#[derive(Row)]
+#[qb(rename_all = "Train-Case")]
+struct MyRow {
+    a: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+mod db {
+    pub use unnamed_qb::*;
+}
+
+#[derive(db::Row)]
+#[qb(crate = "crate::db", table = "users")]
+struct User {
+    name: String,
+}
+
+fn main() {
+    assert_eq!(<User as db::Row>::table(), Some("users"));
+    assert_eq!(<User as db::Row>::columns(), ["name"]);
+}
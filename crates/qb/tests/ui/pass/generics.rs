@@ -0,0 +1,27 @@
+use std::borrow::Cow;
+use unnamed_qb::Row;
+
+#[derive(Row)]
+struct Audit<'a> {
+    created_by: Cow<'a, str>,
+}
+
+#[derive(Row)]
+#[qb(table = "events")]
+struct Event<'a, T: Clone> {
+    name: &'a str,
+    payload: T,
+    #[qb(flatten)]
+    audit: Audit<'a>,
+}
+
+#[derive(Row)]
+struct Pair(#[qb(rename = "key")] String, #[qb(rename = "value")] i64);
+
+fn main() {
+    assert_eq!(
+        <Event<'_, i32> as Row>::columns(),
+        ["name", "payload", "created_by"]
+    );
+    assert_eq!(<Pair as Row>::columns(), ["key", "value"]);
+}
@@ -0,0 +1,19 @@
+use unnamed_qb::{Row, RowBuilder, ValueExt};
+
+struct MyRow {
+    a: i64,
+}
+
+impl<'a> Row<'a> for MyRow {
+    fn columns() -> &'static [&'static str] {
+        &["a"]
+    }
+
+    fn into_row(self, builder: &mut RowBuilder<'a>) {
+        builder.append_binding(self.a.value());
+    }
+}
+
+fn main() {
+    assert_eq!(MyRow::columns(), ["a"]);
+}
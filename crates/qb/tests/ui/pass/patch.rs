@@ -0,0 +1,32 @@
+use unnamed_qb::{sql_dialect::postgres::PostgresSqlDialect, Conditions, Patch, QueryBuilder, ValueExt};
+
+#[derive(Patch)]
+struct Audit {
+    updated_by: Option<i64>,
+}
+
+#[derive(Patch)]
+#[qb(table = "posts", rename_all = "camelCase")]
+struct PostPatch<'a, T> {
+    post_title: Option<&'a str>,
+    payload: Option<T>,
+    deleted_at: Option<Option<i64>>,
+    #[qb(flatten)]
+    audit: Audit,
+}
+
+fn main() {
+    let mut qb = QueryBuilder::patch(PostPatch {
+        post_title: Some("title"),
+        payload: None::<i32>,
+        deleted_at: Some(None),
+        audit: Audit { updated_by: None },
+    })
+    .unwrap();
+    let sql = qb.and_where(("id", "=", 1.value())).sql::<PostgresSqlDialect>();
+
+    assert_eq!(
+        sql.sql,
+        r#"update "posts" set "postTitle" = $1, "deletedAt" = $2 where "id" = $3"#
+    );
+}
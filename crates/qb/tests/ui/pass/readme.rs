@@ -0,0 +1,20 @@
+use unnamed_qb::{sql_dialect::postgres::PostgresSqlDialect, QueryBuilder, Row};
+
+#[derive(Row)]
+struct MyRow {
+    a: String,
+    b: i32,
+}
+
+fn main() {
+    let mut qb = QueryBuilder::insert();
+    let sql = qb
+        .into_("table")
+        .value(MyRow {
+            a: "abc".to_owned(),
+            b: 10,
+        })
+        .sql::<PostgresSqlDialect>();
+
+    assert_eq!(sql.sql, r#"insert into "table" ("a", "b") values ($1, $2)"#);
+}
@@ -0,0 +1,47 @@
+use sqlx::{postgres::PgRow, FromRow};
+use unnamed_qb::{sql_dialect::postgres::PostgresSqlDialect, QueryBuilder, Row};
+
+#[derive(Row, unnamed_qb::FromRow)]
+struct Audit {
+    created_by: String,
+}
+
+#[derive(Row, unnamed_qb::FromRow)]
+#[qb(table = "users", rename_all = "camelCase")]
+struct User<'a, T> {
+    #[qb(skip_insert)]
+    id: i64,
+    #[qb(rename = "mail")]
+    email: &'a str,
+    payload: T,
+    #[qb(skip)]
+    cache: Vec<u8>,
+    #[qb(flatten)]
+    audit: Audit,
+}
+
+fn assert_from_row<'r, R: FromRow<'r, PgRow>>() {}
+
+// any database whose rows decode the field types
+fn assert_generic<'r, R: sqlx::Row>()
+where
+    Audit: FromRow<'r, R>,
+{
+}
+
+fn main() {
+    assert_from_row::<Audit>();
+    assert_from_row::<User<'_, i32>>();
+    assert_generic::<PgRow>();
+
+    let mut qb = QueryBuilder::select();
+    let sql = qb
+        .select_row::<User<'_, i32>>()
+        .from("users")
+        .sql::<PostgresSqlDialect>();
+
+    assert_eq!(
+        sql.sql,
+        r#"select "id", "mail", "payload", "created_by" from "users""#
+    );
+}